@@ -0,0 +1,176 @@
+use std::{fs, process::ExitCode};
+
+use clap::{Parser, Subcommand};
+use utils::{Bench, InputSource, Puzzle};
+
+#[derive(Parser)]
+#[command(about = "Run Advent of Code solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List the days that have solutions
+    List,
+    /// Solve one or more days
+    Run {
+        /// Day numbers to run
+        #[arg(required_unless_present = "all")]
+        days: Vec<u8>,
+        /// Run every day
+        #[arg(long, conflicts_with = "days")]
+        all: bool,
+        /// Read the input from this file instead, or from stdin if "-"
+        #[arg(long, value_name = "PATH", conflicts_with = "variant")]
+        input: Option<String>,
+        /// Read the named input next to each day's Cargo.toml, e.g. "example" for example.txt
+        #[arg(long, value_name = "NAME")]
+        variant: Option<String>,
+        /// Show how long parsing and each part took
+        #[arg(long)]
+        time: bool,
+        /// Solve each day this many times and report min/median/max timings
+        #[arg(long, value_name = "RUNS", num_args = 0..=1, default_missing_value = "10")]
+        bench: Option<usize>,
+        /// Where to write the benchmark results as CSV
+        #[arg(
+            long,
+            value_name = "PATH",
+            default_value = "bench.csv",
+            requires = "bench"
+        )]
+        bench_output: String,
+    },
+}
+
+struct RunOptions {
+    time: bool,
+    bench: Option<usize>,
+    bench_output: String,
+}
+
+fn puzzles() -> Vec<Puzzle> {
+    vec![
+        Puzzle::of::<day1::Day1>(),
+        Puzzle::of::<day2::Day2>(),
+        Puzzle::of::<day3::Day3>(),
+        Puzzle::of::<day4::Day4>(),
+        Puzzle::of::<day5::Day5>(),
+        Puzzle::of::<day6::Day6>(),
+        Puzzle::of::<day7::Day7>(),
+        Puzzle::of::<day8::Day8>(),
+        Puzzle::of::<day9::Day9>(),
+        Puzzle::of::<day10::Day10>(),
+        Puzzle::of::<day11::Day11>(),
+        Puzzle::of::<day12::Day12>(),
+        Puzzle::of::<day13::Day13>(),
+        Puzzle::of::<day14::Day14>(),
+        Puzzle::of::<day15::Day15>(),
+        Puzzle::of::<day16::Day16>(),
+        Puzzle::of::<day17::Day17>(),
+    ]
+}
+
+fn select_puzzles(days: &[u8], all: bool) -> Result<Vec<Puzzle>, String> {
+    let mut puzzles = puzzles();
+    if all {
+        return Ok(puzzles);
+    }
+    if let Some(missing_day) = days
+        .iter()
+        .find(|day| !puzzles.iter().any(|puzzle| puzzle.day == **day))
+    {
+        return Err(format!("no solution for day {missing_day}"));
+    }
+    puzzles.retain(|puzzle| days.contains(&puzzle.day));
+    Ok(puzzles)
+}
+
+fn bench_csv(results: &[(u8, String, Bench)]) -> String {
+    let mut csv = "day,input,stage,runs,min_ns,median_ns,max_ns\n".to_owned();
+    for (day, input_name, bench) in results {
+        for (stage, stats) in &bench.stages {
+            csv.push_str(&format!(
+                "{day},{input_name},{stage},{},{},{},{}\n",
+                bench.runs,
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.max.as_nanos()
+            ));
+        }
+    }
+    csv
+}
+
+fn run(
+    days: &[u8],
+    all: bool,
+    input: Option<String>,
+    variant: Option<String>,
+    options: RunOptions,
+) -> Result<(), String> {
+    let selected = select_puzzles(days, all)?;
+    let source = match (input, variant) {
+        (Some(_), _) if selected.len() > 1 => {
+            return Err("--input can only be used when running a single day".to_owned())
+        }
+        (Some(path), _) => InputSource::from_path_arg(&path),
+        (None, Some(name)) => InputSource::Variant(name),
+        (None, None) => InputSource::default(),
+    };
+
+    let mut bench_results = vec![];
+    for puzzle in selected {
+        println!("== day {} ==", puzzle.day);
+        if let Some(runs) = options.bench {
+            let (answers, bench) = puzzle.bench(&source, runs).map_err(|err| err.to_string())?;
+            print!("{answers}{bench}");
+            bench_results.push((puzzle.day, source.name(), bench));
+        } else {
+            let (answers, timings) = puzzle.run(&source).map_err(|err| err.to_string())?;
+            print!("{answers}");
+            if options.time {
+                print!("{timings}");
+            }
+        }
+    }
+
+    if options.bench.is_some() {
+        fs::write(&options.bench_output, bench_csv(&bench_results))
+            .map_err(|err| format!("failed to write {}: {err}", options.bench_output))?;
+        println!("wrote benchmark results to {}", options.bench_output);
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::List => {
+            for puzzle in puzzles() {
+                println!("day {}", puzzle.day);
+            }
+        }
+        Command::Run {
+            days,
+            all,
+            input,
+            variant,
+            time,
+            bench,
+            bench_output,
+        } => {
+            let options = RunOptions {
+                time,
+                bench,
+                bench_output,
+            };
+            if let Err(message) = run(&days, all, input, variant, options) {
+                eprintln!("error: {message}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
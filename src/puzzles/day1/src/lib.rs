@@ -0,0 +1,55 @@
+use itertools::Itertools;
+
+use utils::{numbered_lines, ParseError, Solution};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    const INPUT_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Vec<u32>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    // Calorie totals for each elf, largest first.
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let line_groups = numbered_lines(input).group_by(|line| !line.text.is_empty());
+        let elf_items = line_groups
+            .into_iter()
+            .filter_map(|(not_empty, elf_lines)| not_empty.then_some(elf_lines));
+        let elf_calorie_totals: Vec<u32> = elf_items
+            .map(|items| {
+                items
+                    .map(|item| item.parse::<u32>(item.text, "a calorie count"))
+                    .sum::<Result<u32, _>>()
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(elf_calorie_totals.into_iter().sorted().rev().collect())
+    }
+
+    fn part1(elf_calorie_totals: &Self::Input) -> u32 {
+        elf_calorie_totals.first().cloned().unwrap_or(0)
+    }
+
+    fn part2(elf_calorie_totals: &Self::Input) -> u32 {
+        elf_calorie_totals.iter().take(3).sum::<u32>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::check_answers;
+
+    use super::Day1;
+
+    #[test]
+    fn example() {
+        check_answers::<Day1>("example");
+    }
+
+    #[test]
+    fn input() {
+        check_answers::<Day1>("input");
+    }
+}
@@ -0,0 +1,95 @@
+use utils::{parse_lines, Line, ParseError, Solution};
+
+pub enum Instruction {
+    Noop,
+    AddX(i32),
+}
+
+fn parse_instruction(line: Line) -> Result<Instruction, ParseError> {
+    let tokens = line.tokens();
+    match tokens.get(0, "noop or addx")? {
+        "noop" => Ok(Instruction::Noop),
+        "addx" => {
+            let amount = tokens.get(1, "an amount to add")?;
+            Ok(Instruction::AddX(line.parse(amount, "an amount to add")?))
+        }
+        other => Err(line.error(other, "noop or addx")),
+    }
+}
+
+// Value of the X register during each cycle.
+fn register_history(instructions: &[Instruction]) -> Vec<i32> {
+    let mut register_x = 1;
+    let mut history = vec![];
+    for instruction in instructions {
+        match instruction {
+            Instruction::Noop => history.push(register_x),
+            Instruction::AddX(amount) => {
+                history.push(register_x);
+                history.push(register_x);
+                register_x += amount;
+            }
+        }
+    }
+    history
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const INPUT_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Vec<Instruction>;
+    type Part1 = i32;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, parse_instruction)
+    }
+
+    fn part1(instructions: &Self::Input) -> i32 {
+        register_history(instructions)
+            .into_iter()
+            .zip(1..)
+            .filter(|(_, cycle)| (cycle % 40) == 20)
+            .map(|(register_x, cycle)| register_x * cycle)
+            .sum::<i32>()
+    }
+
+    fn part2(instructions: &Self::Input) -> String {
+        let pixels: Vec<_> = register_history(instructions)
+            .into_iter()
+            .zip(0..)
+            .map(|(register_x, cycle)| {
+                if ((cycle % 40) - register_x).abs() <= 1 {
+                    '#'
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+
+        pixels
+            .chunks(40)
+            .map(|chunk| chunk.iter().collect::<String>() + "\n")
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::check_answers;
+
+    use super::Day10;
+
+    #[test]
+    fn example() {
+        check_answers::<Day10>("example");
+    }
+
+    #[test]
+    fn input() {
+        check_answers::<Day10>("input");
+    }
+}
@@ -0,0 +1,73 @@
+use std::io::{self, Write};
+
+use itertools::Itertools;
+
+use crate::WorryLevel;
+
+// The state of the monkeys after a round, indexed by monkey.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Round<W> {
+    pub number: u32,
+    // How many items each monkey has inspected since the start.
+    pub inspections: Vec<u64>,
+    pub items: Vec<Vec<W>>,
+}
+
+impl<W: WorryLevel> Round<W> {
+    // In the format of the puzzle's "== After round 1000 ==" listings.
+    pub fn inspections_report(&self) -> String {
+        let mut result = format!("== After round {} ==\n", self.number);
+        for (monkey_idx, inspections) in self.inspections.iter().enumerate() {
+            result += &format!("Monkey {monkey_idx} inspected items {inspections} times.\n");
+        }
+        result
+    }
+
+    pub fn items_report(&self) -> String {
+        let mut result = format!(
+            "After round {}, the monkeys are holding items with these worry levels:\n",
+            self.number
+        );
+        for (monkey_idx, items) in self.items.iter().enumerate() {
+            result += &format!("Monkey {monkey_idx}: {}\n", items.iter().join(", "));
+        }
+        result
+    }
+}
+
+pub struct History<W> {
+    // Only the rounds that were asked for.
+    pub rounds: Vec<Round<W>>,
+    // After the last round, whether or not it was recorded.
+    pub inspections: Vec<u64>,
+}
+
+impl<W: WorryLevel> History<W> {
+    // The product of the two highest inspection counts at the end.
+    pub fn monkey_business_level(&self) -> u64 {
+        self.inspections
+            .iter()
+            .sorted_unstable()
+            .rev()
+            .take(2)
+            .product()
+    }
+
+    // One row per monkey per recorded round, with the items separated by spaces.
+    pub fn write_csv(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "round,monkey,inspections,items")?;
+        for round in &self.rounds {
+            for (monkey_idx, (inspections, items)) in
+                round.inspections.iter().zip(&round.items).enumerate()
+            {
+                let items = items.iter().join(" ").replace('"', "\"\"");
+                writeln!(
+                    out,
+                    "{},{monkey_idx},{inspections},\"{items}\"",
+                    round.number
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
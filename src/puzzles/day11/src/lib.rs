@@ -0,0 +1,506 @@
+mod history;
+mod operation;
+mod trace;
+mod worry;
+
+use std::{
+    collections::VecDeque,
+    fmt::{self, Display},
+};
+
+pub use history::{History, Round};
+use itertools::Itertools;
+pub use operation::{Operation, Operator};
+pub use trace::write_trace;
+use utils::{numbered_lines, Line, ParseError, Solution};
+pub use worry::{Residues, WorryLevel};
+
+#[derive(Clone)]
+pub struct Monkey {
+    // The worry levels of the items it starts with.
+    items: Vec<i64>,
+    operation: Operation,
+    test_divisor: i64,
+    next_monkey_if_true: usize,
+    next_monkey_if_false: usize,
+}
+
+fn take_line<'a>(
+    lines: &mut impl Iterator<Item = Line<'a>>,
+    prefix: &str,
+) -> Result<(Line<'a>, &'a str), ParseError> {
+    let line = lines
+        .next()
+        .ok_or_else(|| ParseError::end_of_input(format!("a line starting with {prefix:?}")))?;
+    let rest = line.strip_prefix(line.text.trim_start(), prefix)?;
+    Ok((line, rest))
+}
+
+fn take_monkey_index_line<'a>(
+    lines: &mut impl Iterator<Item = Line<'a>>,
+    monkey_idx: usize,
+) -> Result<(), ParseError> {
+    let (line, rest) = take_line(lines, "Monkey ")?;
+    let index = line.strip_suffix(rest, ":")?;
+    if line.parse::<usize>(index, "a monkey number")? != monkey_idx {
+        return Err(line.error(index, format!("monkey number {monkey_idx}")));
+    }
+    Ok(())
+}
+
+fn take_starting_items<'a>(
+    lines: &mut impl Iterator<Item = Line<'a>>,
+) -> Result<Vec<i64>, ParseError> {
+    let (line, rest) = take_line(lines, "Starting items: ")?;
+    rest.split(", ")
+        .map(
+            |str_num| match line.parse::<i64>(str_num, "a worry level")? {
+                worry_level @ 0.. => Ok(worry_level),
+                _ => Err(line.error(str_num, "a worry level of at least 0")),
+            },
+        )
+        .collect()
+}
+
+fn take_operation<'a>(lines: &mut impl Iterator<Item = Line<'a>>) -> Result<Operation, ParseError> {
+    let (line, rest) = take_line(lines, "Operation: new = ")?;
+    Operation::parse(&line, rest)
+}
+
+fn take_test_divisor<'a>(lines: &mut impl Iterator<Item = Line<'a>>) -> Result<i64, ParseError> {
+    let (line, rest) = take_line(lines, "Test: divisible by ")?;
+    match line.parse::<i64>(rest, "a divisor")? {
+        divisor @ 1.. => Ok(divisor),
+        _ => Err(line.error(rest, "a positive divisor")),
+    }
+}
+
+// A monkey throwing to itself would keep the item forever.
+fn take_next_monkey<'a>(
+    lines: &mut impl Iterator<Item = Line<'a>>,
+    prefix: &str,
+    monkey_idx: usize,
+    monkey_count: usize,
+) -> Result<usize, ParseError> {
+    let (line, rest) = take_line(lines, prefix)?;
+    let expected = format!("a monkey number below {monkey_count} other than {monkey_idx}");
+    match line.parse::<usize>(rest, &expected)? {
+        next_monkey if next_monkey < monkey_count && next_monkey != monkey_idx => Ok(next_monkey),
+        _ => Err(line.error(rest, expected)),
+    }
+}
+
+fn take_monkey<'a>(
+    mut lines: impl Iterator<Item = Line<'a>>,
+    monkey_idx: usize,
+    monkey_count: usize,
+) -> Result<Monkey, ParseError> {
+    take_monkey_index_line(&mut lines, monkey_idx)?;
+    let starting_items = take_starting_items(&mut lines)?;
+    let operation = take_operation(&mut lines)?;
+    let test_divisor = take_test_divisor(&mut lines)?;
+    let next_monkey_if_true = take_next_monkey(
+        &mut lines,
+        "If true: throw to monkey ",
+        monkey_idx,
+        monkey_count,
+    )?;
+    let next_monkey_if_false = take_next_monkey(
+        &mut lines,
+        "If false: throw to monkey ",
+        monkey_idx,
+        monkey_count,
+    )?;
+    Ok(Monkey {
+        items: starting_items,
+        operation,
+        test_divisor,
+        next_monkey_if_true,
+        next_monkey_if_false,
+    })
+}
+
+fn parse_monkeys(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let line_chunks = numbered_lines(input).chunks(7);
+    let monkey_chunks: Vec<Vec<_>> = line_chunks
+        .into_iter()
+        .map(|chunk| chunk.collect())
+        .collect();
+    let monkey_count = monkey_chunks.len();
+    monkey_chunks
+        .into_iter()
+        .enumerate()
+        .map(|(monkey_idx, chunk)| take_monkey(chunk.into_iter(), monkey_idx, monkey_count))
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Rules {
+    pub rounds: u32,
+    // After each inspection, worry levels are divided by this if there is one.
+    pub relief_divisor: Option<i64>,
+}
+
+impl Rules {
+    pub const PART_1: Self = Self {
+        rounds: 20,
+        relief_divisor: Some(3),
+    };
+    pub const PART_2: Self = Self {
+        rounds: 10000,
+        relief_divisor: None,
+    };
+
+    // The rounds the puzzle shows: every one in part 1, and a few milestones in part 2.
+    pub fn puzzle_rounds(&self) -> Vec<u32> {
+        (1..=self.rounds)
+            .filter(|&round| self.rounds <= 20 || round == 1 || round == 20 || round % 1000 == 0)
+            .collect()
+    }
+
+    // Whether residues can stand in for the exact worry levels.
+    pub fn residues_suffice(&self, monkeys: &[Monkey]) -> bool {
+        self.relief_divisor.is_none()
+            && monkeys
+                .iter()
+                .all(|monkey| !monkey.operation.uses_division())
+    }
+}
+
+// One monkey inspecting and throwing one item.
+pub struct Throw<'a, W> {
+    pub round: u32,
+    pub thrower: usize,
+    pub worry_level: &'a W,
+    pub after_operation: &'a W,
+    // None unless the rules divide worry levels after each inspection.
+    pub after_relief: Option<&'a W>,
+    pub divisible: bool,
+    pub receiver: usize,
+}
+
+impl<W> Throw<'_, W> {
+    // The worry level the item is thrown with.
+    pub fn new_worry_level(&self) -> &W {
+        self.after_relief.unwrap_or(self.after_operation)
+    }
+}
+
+pub struct Simulation<'m, W> {
+    monkeys: &'m [Monkey],
+    divisors: Vec<i64>,
+    relief: Option<W>,
+    // The items each monkey is holding, in the order it'll inspect them.
+    queues: Vec<VecDeque<W>>,
+    inspections: Vec<u64>,
+    rounds_played: u32,
+}
+
+impl<'m, W: WorryLevel> Simulation<'m, W> {
+    pub fn new(monkeys: &'m [Monkey], rules: &Rules) -> Self {
+        assert!(
+            W::SUPPORTS_DIVISION || rules.residues_suffice(monkeys),
+            "these worry levels can't be divided"
+        );
+        let divisors: Vec<_> = monkeys.iter().map(|monkey| monkey.test_divisor).collect();
+        let queues = monkeys
+            .iter()
+            .map(|monkey| {
+                monkey
+                    .items
+                    .iter()
+                    .map(|&item| W::new(item, &divisors))
+                    .collect()
+            })
+            .collect();
+        Self {
+            monkeys,
+            relief: rules
+                .relief_divisor
+                .map(|divisor| W::new(divisor, &divisors)),
+            divisors,
+            queues,
+            inspections: vec![0; monkeys.len()],
+            rounds_played: 0,
+        }
+    }
+
+    // Calls `on_throw` for every item thrown, in order.
+    pub fn play_round(&mut self, mut on_throw: impl FnMut(&Throw<W>)) {
+        let round = self.rounds_played + 1;
+        for (thrower, monkey) in self.monkeys.iter().enumerate() {
+            while let Some(worry_level) = self.queues[thrower].pop_front() {
+                self.inspections[thrower] += 1;
+                let after_operation = monkey.operation.evaluate(&worry_level, &self.divisors);
+                let after_relief = self
+                    .relief
+                    .as_ref()
+                    .map(|relief| after_operation.apply(Operator::Divide, relief));
+                let new_worry_level = after_relief.as_ref().unwrap_or(&after_operation);
+                let divisible = new_worry_level.is_divisible_by(monkey.test_divisor);
+                let receiver = if divisible {
+                    monkey.next_monkey_if_true
+                } else {
+                    monkey.next_monkey_if_false
+                };
+                on_throw(&Throw {
+                    round,
+                    thrower,
+                    worry_level: &worry_level,
+                    after_operation: &after_operation,
+                    after_relief: after_relief.as_ref(),
+                    divisible,
+                    receiver,
+                });
+                self.queues[receiver].push_back(after_relief.unwrap_or(after_operation));
+            }
+        }
+        self.rounds_played = round;
+    }
+
+    pub fn round(&self) -> Round<W> {
+        Round {
+            number: self.rounds_played,
+            inspections: self.inspections.clone(),
+            items: self
+                .queues
+                .iter()
+                .map(|queue| queue.iter().cloned().collect())
+                .collect(),
+        }
+    }
+}
+
+// Records the rounds for which `record_round` returns true.
+pub fn simulate<W: WorryLevel>(
+    monkeys: &[Monkey],
+    rules: &Rules,
+    mut record_round: impl FnMut(u32) -> bool,
+) -> History<W> {
+    let mut simulation = Simulation::new(monkeys, rules);
+    let mut rounds = vec![];
+    for round in 1..=rules.rounds {
+        simulation.play_round(|_| {});
+        if record_round(round) {
+            rounds.push(simulation.round());
+        }
+    }
+    History {
+        rounds,
+        inspections: simulation.inspections,
+    }
+}
+
+pub fn monkey_business_level<W: WorryLevel>(monkeys: &[Monkey], rules: &Rules) -> u64 {
+    simulate::<W>(monkeys, rules, |_| false).monkey_business_level()
+}
+
+// Part 2's answer. Without relief the worry levels are only kept small as residues, which can't
+// be divided, so there's none for monkeys whose operations divide: exact worry levels grow with
+// every round and 10000 rounds of them would never finish.
+pub struct MonkeyBusiness(pub Option<u64>);
+
+impl Display for MonkeyBusiness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(level) => write!(f, "{level}"),
+            None => write!(
+                f,
+                "none, since a monkey's operation divides its worry level"
+            ),
+        }
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const INPUT_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Vec<Monkey>;
+    type Part1 = u64;
+    type Part2 = MonkeyBusiness;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_monkeys(input)
+    }
+
+    fn part1(monkeys: &Self::Input) -> u64 {
+        monkey_business_level::<i64>(monkeys, &Rules::PART_1)
+    }
+
+    // Worry levels get far too big to keep exactly over 10000 rounds, but the tests only need them
+    // modulo each divisor.
+    fn part2(monkeys: &Self::Input) -> MonkeyBusiness {
+        let rules = Rules::PART_2;
+        MonkeyBusiness(
+            rules
+                .residues_suffice(monkeys)
+                .then(|| monkey_business_level::<Residues>(monkeys, &rules)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use utils::{check_answers, read_input, InputSource, Solution};
+
+    use super::{monkey_business_level, simulate, write_trace, Day11, Residues, Rules};
+
+    #[test]
+    fn example() {
+        check_answers::<Day11>("example");
+    }
+
+    #[test]
+    fn input() {
+        check_answers::<Day11>("input");
+    }
+
+    #[test]
+    fn worry_level_types_agree() {
+        for variant in [
+            InputSource::Variant("example".to_owned()),
+            InputSource::default(),
+        ] {
+            let input = read_input(Day11::INPUT_DIR, &variant).unwrap();
+            let monkeys = Day11::parse(&input).unwrap();
+            assert_eq!(
+                monkey_business_level::<i64>(&monkeys, &Rules::PART_1),
+                monkey_business_level::<BigUint>(&monkeys, &Rules::PART_1)
+            );
+            // Without relief the exact worry levels soon stop fitting in an i64.
+            let no_relief = |rounds| Rules {
+                rounds,
+                relief_divisor: None,
+            };
+            assert_eq!(
+                monkey_business_level::<BigUint>(&monkeys, &no_relief(12)),
+                monkey_business_level::<Residues>(&monkeys, &no_relief(12))
+            );
+            assert_eq!(
+                monkey_business_level::<i64>(&monkeys, &no_relief(2)),
+                monkey_business_level::<Residues>(&monkeys, &no_relief(2))
+            );
+        }
+    }
+
+    #[test]
+    fn malformed_monkeys() {
+        let input = read_input(
+            Day11::INPUT_DIR,
+            &InputSource::Variant("example".to_owned()),
+        )
+        .unwrap();
+        let err = Day11::parse(&input.replace("by 23", "by 0")).err().unwrap();
+        assert_eq!((err.line, err.column, err.found.as_str()), (4, 22, "0"));
+        assert_eq!(err.expected, "a positive divisor");
+
+        let err = Day11::parse(&input.replace("Monkey 1:", "Monkey 2:"))
+            .err()
+            .unwrap();
+        assert_eq!((err.line, err.column, err.found.as_str()), (8, 8, "2"));
+        assert_eq!(err.expected, "monkey number 1");
+
+        let err = Day11::parse(&input.replace("monkey 3", "monkey 0"))
+            .err()
+            .unwrap();
+        assert_eq!((err.line, err.column, err.found.as_str()), (6, 31, "0"));
+        assert_eq!(err.expected, "a monkey number below 4 other than 0");
+
+        let err = Day11::parse(&input.replace("items: 79, 98", "items: 79 98"))
+            .err()
+            .unwrap();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 19, "79 98"));
+        assert_eq!(err.expected, "a worry level");
+
+        let truncated: String = input
+            .lines()
+            .take(3)
+            .map(|line| format!("{line}\n"))
+            .collect();
+        let err = Day11::parse(&truncated).err().unwrap();
+        assert_eq!(err.line, 0);
+        assert_eq!(err.expected, "a line starting with \"Test: divisible by \"");
+    }
+
+    #[test]
+    fn part_2_needs_monkeys_that_dont_divide() {
+        let input = read_input(
+            Day11::INPUT_DIR,
+            &InputSource::Variant("example".to_owned()),
+        );
+        let input = input.unwrap().replace("old * 19", "old / 2");
+        let monkeys = Day11::parse(&input).unwrap();
+        assert_eq!(Day11::part2(&monkeys).0, None);
+    }
+
+    #[test]
+    fn example_history() {
+        let input = read_input(
+            Day11::INPUT_DIR,
+            &InputSource::Variant("example".to_owned()),
+        );
+        let monkeys = Day11::parse(&input.unwrap()).unwrap();
+        let part_1 = simulate::<i64>(&monkeys, &Rules::PART_1, |round| round == 1);
+        assert_eq!(
+            part_1.rounds[0].items_report(),
+            "After round 1, the monkeys are holding items with these worry levels:\n\
+             Monkey 0: 20, 23, 27, 26\n\
+             Monkey 1: 2080, 25, 167, 207, 401, 1046\n\
+             Monkey 2: \n\
+             Monkey 3: \n"
+        );
+        assert_eq!(part_1.inspections, vec![101, 95, 7, 105]);
+
+        let rules = Rules::PART_2;
+        let puzzle_rounds = rules.puzzle_rounds();
+        let part_2 = simulate::<Residues>(&monkeys, &rules, |round| puzzle_rounds.contains(&round));
+        assert_eq!(part_2.rounds.len(), 12);
+        assert_eq!(
+            part_2.rounds[2].inspections_report(),
+            "== After round 1000 ==\n\
+             Monkey 0 inspected items 5204 times.\n\
+             Monkey 1 inspected items 4792 times.\n\
+             Monkey 2 inspected items 199 times.\n\
+             Monkey 3 inspected items 5192 times.\n"
+        );
+
+        let mut csv = vec![];
+        part_2.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), 1 + 12 * 4);
+        assert!(csv.contains("\n1000,0,5204,\""));
+    }
+
+    #[test]
+    fn trace_matches_puzzle() {
+        let input = read_input(
+            Day11::INPUT_DIR,
+            &InputSource::Variant("example".to_owned()),
+        );
+        let monkeys = Day11::parse(&input.unwrap()).unwrap();
+        let mut trace = vec![];
+        write_trace::<i64>(&monkeys, &Rules::PART_1, 1, &mut trace).unwrap();
+        let trace = String::from_utf8(trace).unwrap();
+        assert!(trace.starts_with(
+            "Monkey 0:\n\
+             \x20 Monkey inspects an item with a worry level of 79.\n\
+             \x20   Worry level is multiplied by 19 to 1501.\n\
+             \x20   Monkey gets bored with item. Worry level is divided by 3 to 500.\n\
+             \x20   Current worry level is not divisible by 23.\n\
+             \x20   Item with worry level 500 is thrown to monkey 3.\n"
+        ));
+        assert!(trace.contains(
+            "Monkey 2:\n\
+             \x20 Monkey inspects an item with a worry level of 79.\n\
+             \x20   Worry level is multiplied by itself to 6241.\n"
+        ));
+        assert!(trace.contains("    Worry level increases by 6 to 60.\n"));
+        assert!(
+            trace.ends_with("Monkey 1: 2080, 25, 167, 207, 401, 1046\nMonkey 2: \nMonkey 3: \n")
+        );
+    }
+}
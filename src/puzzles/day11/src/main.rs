@@ -1,168 +1,181 @@
-use itertools::Itertools;
-use utils::read_input;
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+};
 
-struct Monkey {
-    items: Vec<i64>,
-    compute_new_worry_level: Box<dyn FnMut(i64) -> i64>,
-    test_divisor: i64,
-    next_monkey_if_true: usize,
-    next_monkey_if_false: usize,
-    activity: u64,
-}
+use day11::{simulate, write_trace, Day11, Monkey, Residues, Rules, WorryLevel};
+use num_bigint::BigUint;
+use utils::{exit_with_error, take_flag, take_number, take_value};
 
-fn take_monkey_index_line<'a>(lines: &mut impl Iterator<Item = &'a str>) {
-    lines.next();
+struct HistoryOptions {
+    rules: Rules,
+    // None for the rounds the puzzle shows.
+    rounds: Option<Vec<u32>>,
+    show_items: bool,
+    csv_path: Option<String>,
 }
 
-fn take_starting_items<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Vec<i64> {
-    lines
-        .next()
-        .map(|line| {
-            line.trim()
-                .strip_prefix("Starting items: ")
-                .unwrap()
-                .split(", ")
-                .map(|str_num| str_num.parse::<i64>().unwrap())
-                .collect()
-        })
-        .unwrap()
-}
+impl HistoryOptions {
+    // Returns None unless --history was given.
+    fn take(args: &mut Vec<String>) -> Result<Option<Self>, String> {
+        let rules = match take_number::<u8>(args, "--history")? {
+            None => return Ok(None),
+            Some(1) => Rules::PART_1,
+            Some(2) => Rules::PART_2,
+            Some(part) => {
+                return Err(format!(
+                    "expected part 1 or 2 after --history, found {part}"
+                ))
+            }
+        };
+        let rounds = take_value(args, "--rounds")?
+            .map(|rounds| parse_rounds(&rounds, rules.rounds))
+            .transpose()?;
+        Ok(Some(Self {
+            rules,
+            rounds,
+            show_items: take_flag(args, "--items"),
+            csv_path: take_value(args, "--csv")?,
+        }))
+    }
 
-fn take_compute_new_worry_level<'a>(
-    lines: &mut impl Iterator<Item = &'a str>,
-) -> Box<dyn FnMut(i64) -> i64> {
-    lines
-        .next()
-        .map(|line| {
-            let line = line.to_owned();
-            let parts: Vec<_> = line
-                .trim()
-                .strip_prefix("Operation: new = ")
-                .unwrap()
-                .split(' ')
-                .map(|part| part.to_owned())
-                .collect();
+    fn report<W: WorryLevel>(&self, monkeys: &[Monkey]) -> Result<(), String> {
+        let rounds = self
+            .rounds
+            .clone()
+            .unwrap_or_else(|| self.rules.puzzle_rounds());
+        let history = simulate::<W>(monkeys, &self.rules, |round| rounds.contains(&round));
 
-            Box::new(move |old_worry_level: i64| {
-                let lhs = match parts[0].as_str() {
-                    "old" => old_worry_level,
-                    other_str => other_str.parse::<i64>().unwrap(),
-                };
-                let rhs = match parts[2].as_str() {
-                    "old" => old_worry_level,
-                    other_str => other_str.parse::<i64>().unwrap(),
-                };
-                match parts[1].as_str() {
-                    "*" => lhs * rhs,
-                    "+" => lhs + rhs,
-                    _ => panic!(),
-                }
-            })
-        })
-        .unwrap()
+        if let Some(path) = &self.csv_path {
+            let mut csv = vec![];
+            history.write_csv(&mut csv).unwrap();
+            return fs::write(path, csv).map_err(|err| format!("failed to write {path}: {err}"));
+        }
+        for round in &history.rounds {
+            if self.show_items {
+                println!("{}", round.items_report());
+            }
+            println!("{}", round.inspections_report());
+        }
+        println!("monkey business: {}", history.monkey_business_level());
+        Ok(())
+    }
 }
 
-fn take_test_divisor<'a>(lines: &mut impl Iterator<Item = &'a str>) -> i64 {
-    lines
-        .next()
-        .unwrap()
-        .trim()
-        .strip_prefix("Test: divisible by ")
-        .unwrap()
-        .parse::<i64>()
-        .unwrap()
+struct TraceOptions {
+    rules: Rules,
+    rounds: u32,
+    // Stdout if there isn't one.
+    output: Option<String>,
 }
 
-fn take_next_monkey_if_true<'a>(lines: &mut impl Iterator<Item = &'a str>) -> usize {
-    lines
-        .next()
-        .unwrap()
-        .trim()
-        .strip_prefix("If true: throw to monkey ")
-        .unwrap()
-        .parse()
-        .unwrap()
+impl TraceOptions {
+    // Returns None unless --trace was given.
+    fn take(args: &mut Vec<String>) -> Result<Option<Self>, String> {
+        let Some(rounds) = take_number(args, "--trace")? else {
+            return Ok(None);
+        };
+        let rules = match take_number::<u8>(args, "--trace-part")? {
+            None | Some(1) => Rules::PART_1,
+            Some(2) => Rules::PART_2,
+            Some(part) => {
+                return Err(format!(
+                    "expected part 1 or 2 after --trace-part, found {part}"
+                ))
+            }
+        };
+        Ok(Some(Self {
+            rules,
+            rounds,
+            output: take_value(args, "--trace-output")?,
+        }))
+    }
+
+    fn write<W: WorryLevel>(&self, monkeys: &[Monkey]) -> Result<(), String> {
+        let mut out: Box<dyn Write> = match &self.output {
+            Some(path) => {
+                let file =
+                    File::create(path).map_err(|err| format!("failed to create {path}: {err}"))?;
+                Box::new(BufWriter::new(file))
+            }
+            None => Box::new(io::stdout().lock()),
+        };
+        write_trace::<W>(monkeys, &self.rules, self.rounds, &mut out)
+            .and_then(|_| out.flush())
+            .map_err(|err| format!("failed to write the trace: {err}"))
+    }
 }
 
-fn take_next_monkey_if_false<'a>(lines: &mut impl Iterator<Item = &'a str>) -> usize {
-    lines
-        .next()
-        .unwrap()
-        .trim()
-        .strip_prefix("If false: throw to monkey ")
-        .unwrap()
-        .parse()
-        .unwrap()
+enum Command {
+    History(HistoryOptions),
+    Trace(TraceOptions),
 }
 
-fn take_monkey<'a>(mut lines: impl Iterator<Item = &'a str>) -> Monkey {
-    take_monkey_index_line(&mut lines);
-    let starting_items = take_starting_items(&mut lines);
-    let compute_new_worry_level = take_compute_new_worry_level(&mut lines);
-    let test_divisor = take_test_divisor(&mut lines);
-    let next_monkey_if_true = take_next_monkey_if_true(&mut lines);
-    let next_monkey_if_false = take_next_monkey_if_false(&mut lines);
-    Monkey {
-        items: starting_items,
-        compute_new_worry_level,
-        test_divisor,
-        next_monkey_if_true,
-        next_monkey_if_false,
-        activity: 0,
+impl Command {
+    // Returns None unless --history or --trace was given.
+    fn take(args: &mut Vec<String>) -> Result<Option<Self>, String> {
+        match (HistoryOptions::take(args)?, TraceOptions::take(args)?) {
+            (None, None) => Ok(None),
+            (Some(history), None) => Ok(Some(Self::History(history))),
+            (None, Some(trace)) => Ok(Some(Self::Trace(trace))),
+            (Some(_), Some(_)) => Err("expected only one of --history and --trace".to_owned()),
+        }
     }
-}
 
-fn parse_monkeys(input: String) -> Vec<Monkey> {
-    input
-        .lines()
-        .chunks(7)
-        .into_iter()
-        .map(take_monkey)
-        .collect()
-}
+    fn rules(&self) -> &Rules {
+        match self {
+            Self::History(options) => &options.rules,
+            Self::Trace(options) => &options.rules,
+        }
+    }
 
-fn monkey_business_level<F: Fn(i64) -> i64>(
-    mut monkeys: Vec<Monkey>,
-    rounds: u32,
-    worry_level_adjustment: F,
-) -> u64 {
-    (0..rounds).for_each(|_| {
-        (0..monkeys.len()).for_each(|monkey_idx| {
-            while !monkeys[monkey_idx].items.is_empty() {
-                monkeys[monkey_idx].activity += 1;
-                let item = monkeys[monkey_idx].items.remove(0);
-                let new_worry_level =
-                    worry_level_adjustment((monkeys[monkey_idx].compute_new_worry_level)(item));
-                let next_monkey = if new_worry_level % monkeys[monkey_idx].test_divisor == 0 {
-                    monkeys[monkey_idx].next_monkey_if_true
-                } else {
-                    monkeys[monkey_idx].next_monkey_if_false
-                };
-                monkeys[next_monkey].items.push(new_worry_level);
-            }
-        });
-    });
+    fn run<W: WorryLevel>(&self, monkeys: &[Monkey]) -> Result<(), String> {
+        match self {
+            Self::History(options) => options.report::<W>(monkeys),
+            Self::Trace(options) => options.write::<W>(monkeys),
+        }
+    }
+}
 
-    monkeys.sort_by_key(|monkey| monkey.activity);
-    monkeys.reverse();
-    monkeys
-        .into_iter()
-        .take(2)
-        .map(|monkey| monkey.activity)
-        .product()
+// "all", or round numbers separated by commas.
+fn parse_rounds(rounds: &str, last_round: u32) -> Result<Vec<u32>, String> {
+    if rounds == "all" {
+        return Ok((1..=last_round).collect());
+    }
+    rounds
+        .split(',')
+        .map(|round| match round.trim().parse() {
+            Ok(round @ 1..) if round <= last_round => Ok(round),
+            _ => Err(format!(
+                "expected \"all\" or rounds from 1 to {last_round}, found {round}"
+            )),
+        })
+        .collect()
 }
 
 fn main() {
-    let monkeys = parse_monkeys(read_input());
-    println!(
-        "part 1: {}",
-        monkey_business_level(monkeys, 20, |worry_level| worry_level / 3)
-    );
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let command = Command::take(&mut args).unwrap_or_else(|message| exit_with_error(message));
+    let Some(command) = command else {
+        return utils::run_with_args::<Day11>(args.into_iter());
+    };
+    let monkeys = utils::load_input::<Day11>(args.into_iter());
 
-    let monkeys = parse_monkeys(read_input());
-    let divisor_product: i64 = monkeys.iter().map(|monkey| monkey.test_divisor).product();
-    println!(
-        "part 2: {}",
-        monkey_business_level(monkeys, 10000, |worry_level| worry_level % divisor_product)
-    );
+    // The same worry levels the answers are worked out with. Exact ones are only kept without
+    // relief for a trace, which stops after the rounds asked for; a history plays every round.
+    let rules = command.rules();
+    let result = if rules.relief_divisor.is_some() {
+        command.run::<i64>(&monkeys)
+    } else if rules.residues_suffice(&monkeys) {
+        command.run::<Residues>(&monkeys)
+    } else if let Command::Trace(_) = command {
+        command.run::<BigUint>(&monkeys)
+    } else {
+        Err(format!(
+            "expected monkeys whose operations don't divide for part 2's {} rounds",
+            rules.rounds
+        ))
+    };
+    result.unwrap_or_else(|message| exit_with_error(message));
 }
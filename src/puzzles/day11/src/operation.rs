@@ -0,0 +1,288 @@
+use std::fmt::Display;
+
+use utils::{Line, ParseError};
+
+use crate::WorryLevel;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+}
+
+impl Operator {
+    fn from_token(token: &str) -> Option<Self> {
+        match token {
+            "+" => Some(Self::Add),
+            "-" => Some(Self::Subtract),
+            "*" => Some(Self::Multiply),
+            "/" => Some(Self::Divide),
+            "%" => Some(Self::Remainder),
+            _ => None,
+        }
+    }
+
+    fn symbol(&self) -> char {
+        match self {
+            Self::Add => '+',
+            Self::Subtract => '-',
+            Self::Multiply => '*',
+            Self::Divide => '/',
+            Self::Remainder => '%',
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Add | Self::Subtract => 1,
+            Self::Multiply | Self::Divide | Self::Remainder => 2,
+        }
+    }
+
+    pub fn divides(&self) -> bool {
+        matches!(self, Self::Divide | Self::Remainder)
+    }
+}
+
+// The right hand side of "Operation: new = ...", in terms of the old worry level.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Operation {
+    Old,
+    Constant(i64),
+    Binary(Operator, Box<Operation>, Box<Operation>),
+}
+
+impl Operation {
+    pub fn parse<'a>(line: &Line<'a>, text: &'a str) -> Result<Self, ParseError> {
+        let mut parser = Parser {
+            line,
+            tokens: tokenize(line, text)?,
+            next_idx: 0,
+        };
+        let operation = parser.expression()?;
+        match parser.peek() {
+            Some(token) => Err(line.error(token, "an operator")),
+            None => Ok(operation),
+        }
+    }
+
+    // `divisors` are the monkeys' test divisors, which some worry level types need to make
+    // constants.
+    pub fn evaluate<W: WorryLevel>(&self, old: &W, divisors: &[i64]) -> W {
+        match self {
+            Self::Old => old.clone(),
+            Self::Constant(value) => W::new(*value, divisors),
+            Self::Binary(operator, lhs, rhs) => lhs
+                .evaluate(old, divisors)
+                .apply(*operator, &rhs.evaluate(old, divisors)),
+        }
+    }
+
+    pub fn uses_division(&self) -> bool {
+        match self {
+            Self::Old | Self::Constant(_) => false,
+            Self::Binary(operator, lhs, rhs) => {
+                operator.divides() || lhs.uses_division() || rhs.uses_division()
+            }
+        }
+    }
+
+    // "old * old", which grows worry levels much faster than anything else the monkeys do.
+    pub fn is_square(&self) -> bool {
+        match self {
+            Self::Binary(Operator::Multiply, lhs, rhs) => **lhs == Self::Old && **rhs == Self::Old,
+            _ => false,
+        }
+    }
+
+    // How the puzzle's walkthrough words the operation, e.g. "is multiplied by 19", falling back
+    // to the expression itself for anything it doesn't have words for.
+    pub fn describe(&self) -> String {
+        match self {
+            _ if self.is_square() => "is multiplied by itself".to_owned(),
+            Self::Binary(operator, lhs, rhs) if **lhs == Self::Old => match (operator, &**rhs) {
+                (Operator::Add, Self::Constant(value)) => format!("increases by {value}"),
+                (Operator::Subtract, Self::Constant(value)) => format!("decreases by {value}"),
+                (Operator::Multiply, Self::Constant(value)) => format!("is multiplied by {value}"),
+                (Operator::Divide, Self::Constant(value)) => format!("is divided by {value}"),
+                _ => format!("becomes {self}"),
+            },
+            _ => format!("becomes {self}"),
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Binary(operator, _, _) => operator.precedence(),
+            _ => u8::MAX,
+        }
+    }
+}
+
+// Only puts in the parentheses that are needed, so "old * 19" prints as it was written.
+impl Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Old => write!(f, "old"),
+            Self::Constant(value) => write!(f, "{value}"),
+            Self::Binary(operator, lhs, rhs) => {
+                // Everything is left associative, so the right hand side needs parentheses even
+                // when its operator binds just as tightly.
+                if lhs.precedence() < operator.precedence() {
+                    write!(f, "({lhs})")?;
+                } else {
+                    write!(f, "{lhs}")?;
+                }
+                write!(f, " {} ", operator.symbol())?;
+                if rhs.precedence() <= operator.precedence() {
+                    write!(f, "({rhs})")
+                } else {
+                    write!(f, "{rhs}")
+                }
+            }
+        }
+    }
+}
+
+// Splits into numbers, words, operators and parentheses, each a slice of the line so errors can
+// point at them.
+fn tokenize<'a>(line: &Line<'a>, text: &'a str) -> Result<Vec<&'a str>, ParseError> {
+    let mut tokens = vec![];
+    let mut rest = text.trim_start();
+    while let Some(ch) = rest.chars().next() {
+        let len = if ch.is_ascii_alphanumeric() {
+            rest.find(|c: char| !c.is_ascii_alphanumeric())
+                .unwrap_or(rest.len())
+        } else if "+-*/%()".contains(ch) {
+            1
+        } else {
+            let found = &rest[..ch.len_utf8()];
+            return Err(line.error(found, "a number, \"old\", an operator or a parenthesis"));
+        };
+        tokens.push(&rest[..len]);
+        rest = rest[len..].trim_start();
+    }
+    Ok(tokens)
+}
+
+// Recursive descent, with * / and % binding tighter than + and -.
+struct Parser<'l, 'a> {
+    line: &'l Line<'a>,
+    tokens: Vec<&'a str>,
+    next_idx: usize,
+}
+
+impl<'a> Parser<'_, 'a> {
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.next_idx).copied()
+    }
+
+    fn next(&mut self, expected: &str) -> Result<&'a str, ParseError> {
+        let token = self
+            .peek()
+            .ok_or_else(|| self.line.error_at_end(expected))?;
+        self.next_idx += 1;
+        Ok(token)
+    }
+
+    fn binary(
+        &mut self,
+        precedence: u8,
+        mut operand: impl FnMut(&mut Self) -> Result<Operation, ParseError>,
+    ) -> Result<Operation, ParseError> {
+        let mut lhs = operand(self)?;
+        while let Some(operator) = self
+            .peek()
+            .and_then(Operator::from_token)
+            .filter(|operator| operator.precedence() == precedence)
+        {
+            self.next_idx += 1;
+            let rhs_token = self.peek();
+            let rhs = operand(self)?;
+            // Anything else that comes out as 0 is only found when it's evaluated.
+            if operator.divides() && rhs == Operation::Constant(0) {
+                let found = rhs_token.unwrap_or_default();
+                return Err(self.line.error(found, "a divisor other than 0"));
+            }
+            lhs = Operation::Binary(operator, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn expression(&mut self) -> Result<Operation, ParseError> {
+        self.binary(1, |parser| parser.term())
+    }
+
+    fn term(&mut self) -> Result<Operation, ParseError> {
+        self.binary(2, |parser| parser.operand())
+    }
+
+    fn operand(&mut self) -> Result<Operation, ParseError> {
+        let expected = "\"old\", a number or (";
+        let token = self.next(expected)?;
+        match token {
+            "old" => Ok(Operation::Old),
+            "(" => {
+                let operation = self.expression()?;
+                match self.next(")")? {
+                    ")" => Ok(operation),
+                    other => Err(self.line.error(other, ")")),
+                }
+            }
+            _ => self
+                .line
+                .parse::<i64>(token, expected)
+                .map(Operation::Constant),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::numbered_lines;
+
+    use super::Operation;
+
+    fn parse(text: &str) -> Operation {
+        let line = numbered_lines(text).next().unwrap();
+        Operation::parse(&line, line.text).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        for text in [
+            "old * 19",
+            "old + 6",
+            "old * old",
+            "(old + 3) * 2 % 7",
+            "old - (old - 1)",
+        ] {
+            assert_eq!(parse(text).to_string(), text);
+        }
+        assert_eq!(parse("((old)*(2))").to_string(), "old * 2");
+        assert_eq!(parse("old + 2 * 3 - 10 / 4").evaluate(&5, &[]), 9);
+        assert_eq!(parse("(old + 2) * 3").evaluate(&5, &[]), 21);
+        assert!(parse("(old + 2) % 3").uses_division());
+        assert!(!parse("(old + 2) * 3").uses_division());
+        assert!(parse("old * old").is_square());
+        assert!(!parse("old * 2").is_square());
+
+        let line = numbered_lines("old * (2").next().unwrap();
+        assert!(Operation::parse(&line, line.text).is_err());
+        let line = numbered_lines("old ^ 2").next().unwrap();
+        assert!(Operation::parse(&line, line.text).is_err());
+    }
+
+    #[test]
+    fn dividing_by_zero_is_rejected() {
+        for (text, column) in [("old / 0", 7), ("old % (0)", 7), ("3 + old / 0 * 2", 11)] {
+            let line = numbered_lines(text).next().unwrap();
+            let err = Operation::parse(&line, line.text).unwrap_err();
+            assert_eq!(err.column, column, "{text}");
+            assert_eq!(err.expected, "a divisor other than 0");
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::io::{self, Write};
+
+use crate::{Monkey, Rules, Simulation, Throw, WorryLevel};
+
+fn write_throw<W: WorryLevel>(
+    monkeys: &[Monkey],
+    rules: &Rules,
+    throw: &Throw<W>,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let monkey = &monkeys[throw.thrower];
+    writeln!(
+        out,
+        "  Monkey inspects an item with a worry level of {}.",
+        throw.worry_level
+    )?;
+    writeln!(
+        out,
+        "    Worry level {} to {}.",
+        monkey.operation.describe(),
+        throw.after_operation
+    )?;
+    if let (Some(after_relief), Some(divisor)) = (throw.after_relief, rules.relief_divisor) {
+        writeln!(
+            out,
+            "    Monkey gets bored with item. Worry level is divided by {divisor} to {after_relief}."
+        )?;
+    }
+    let not = if throw.divisible { "" } else { "not " };
+    writeln!(
+        out,
+        "    Current worry level is {not}divisible by {}.",
+        monkey.test_divisor
+    )?;
+    writeln!(
+        out,
+        "    Item with worry level {} is thrown to monkey {}.",
+        throw.new_worry_level(),
+        throw.receiver
+    )
+}
+
+// Every inspection and throw of the first `rounds` rounds, worded like the puzzle's walkthrough,
+// with the items each monkey is holding after each round. Monkeys with nothing to throw are left
+// out.
+pub fn write_trace<W: WorryLevel>(
+    monkeys: &[Monkey],
+    rules: &Rules,
+    rounds: u32,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let mut simulation = Simulation::<W>::new(monkeys, rules);
+    for round in 0..rounds.min(rules.rounds) {
+        if round > 0 {
+            writeln!(out)?;
+        }
+        // Writing to a Vec can't fail, so the errors only need handling once per round.
+        let mut buffer = vec![];
+        let mut current_thrower = None;
+        simulation.play_round(|throw| {
+            if current_thrower != Some(throw.thrower) {
+                current_thrower = Some(throw.thrower);
+                writeln!(buffer, "Monkey {}:", throw.thrower).unwrap();
+            }
+            write_throw(monkeys, rules, throw, &mut buffer).unwrap();
+        });
+        out.write_all(&buffer)?;
+        writeln!(out)?;
+        write!(out, "{}", simulation.round().items_report())?;
+    }
+    Ok(())
+}
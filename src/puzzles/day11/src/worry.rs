@@ -0,0 +1,128 @@
+use std::fmt::{Debug, Display};
+
+use num_bigint::BigUint;
+use num_traits::{CheckedSub, Zero};
+
+use crate::Operator;
+
+// How the simulation keeps track of worry levels.
+pub trait WorryLevel: Clone + Debug + Display {
+    // Residues can't be divided, since they only keep what the monkeys' tests need.
+    const SUPPORTS_DIVISION: bool = true;
+
+    // `divisors` are the test divisors of every monkey.
+    fn new(value: i64, divisors: &[i64]) -> Self;
+
+    fn apply(&self, operator: Operator, rhs: &Self) -> Self;
+
+    fn is_divisible_by(&self, divisor: i64) -> bool;
+}
+
+// Exact, but only for as long as the worry levels fit.
+impl WorryLevel for i64 {
+    fn new(value: i64, _divisors: &[i64]) -> Self {
+        value
+    }
+
+    fn apply(&self, operator: Operator, rhs: &Self) -> Self {
+        let (lhs, rhs) = (*self, *rhs);
+        let result = match operator {
+            Operator::Add => lhs.checked_add(rhs),
+            Operator::Subtract => lhs.checked_sub(rhs),
+            Operator::Multiply => lhs.checked_mul(rhs),
+            Operator::Divide => lhs.checked_div(rhs),
+            Operator::Remainder => lhs.checked_rem(rhs),
+        };
+        result.unwrap_or_else(|| {
+            // Parsing rejects dividing by a constant 0, but not by something that comes out as 0.
+            if operator.divides() && rhs == 0 {
+                panic!("{lhs} {operator:?} {rhs} divides by zero")
+            }
+            panic!("{lhs} {operator:?} {rhs} doesn't fit in an i64")
+        })
+    }
+
+    fn is_divisible_by(&self, divisor: i64) -> bool {
+        self % divisor == 0
+    }
+}
+
+// Exact, however big the worry levels get, as long as they stay positive.
+impl WorryLevel for BigUint {
+    fn new(value: i64, _divisors: &[i64]) -> Self {
+        BigUint::try_from(value).expect("worry levels can't be negative")
+    }
+
+    fn apply(&self, operator: Operator, rhs: &Self) -> Self {
+        match operator {
+            Operator::Add => self + rhs,
+            Operator::Subtract => self
+                .checked_sub(rhs)
+                .expect("worry levels can't be negative"),
+            Operator::Multiply => self * rhs,
+            Operator::Divide => self / rhs,
+            Operator::Remainder => self % rhs,
+        }
+    }
+
+    fn is_divisible_by(&self, divisor: i64) -> bool {
+        (self % divisor as u64).is_zero()
+    }
+}
+
+// The worry level modulo each monkey's divisor, as (divisor, residue) pairs. Adding, subtracting
+// and multiplying keep the residues right, so the tests come out the same as with the exact value,
+// and nothing ever grows.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Residues(Vec<(i64, i64)>);
+
+impl WorryLevel for Residues {
+    const SUPPORTS_DIVISION: bool = false;
+
+    fn new(value: i64, divisors: &[i64]) -> Self {
+        Self(
+            divisors
+                .iter()
+                .map(|&divisor| (divisor, value.rem_euclid(divisor)))
+                .collect(),
+        )
+    }
+
+    fn apply(&self, operator: Operator, rhs: &Self) -> Self {
+        let residues = self
+            .0
+            .iter()
+            .zip(&rhs.0)
+            .map(|(&(divisor, lhs), &(_, rhs))| {
+                let result = match operator {
+                    Operator::Add => lhs + rhs,
+                    Operator::Subtract => lhs - rhs,
+                    Operator::Multiply => (lhs as i128 * rhs as i128 % divisor as i128) as i64,
+                    Operator::Divide | Operator::Remainder => {
+                        panic!("residues can't be used with {operator:?}")
+                    }
+                };
+                (divisor, result.rem_euclid(divisor))
+            });
+        Self(residues.collect())
+    }
+
+    fn is_divisible_by(&self, divisor: i64) -> bool {
+        let (_, residue) = self
+            .0
+            .iter()
+            .find(|(other_divisor, _)| *other_divisor == divisor)
+            .expect("residues are kept for every monkey's divisor");
+        *residue == 0
+    }
+}
+
+impl Display for Residues {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let residues = self
+            .0
+            .iter()
+            .map(|(divisor, residue)| format!("{residue} mod {divisor}"));
+        write!(f, "({})", residues.collect::<Vec<_>>().join(", "))
+    }
+}
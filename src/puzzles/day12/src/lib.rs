@@ -0,0 +1,191 @@
+use std::{cmp::Ordering, fmt::Display};
+
+use utils::{Graph, Grid, ParseError, Position, Solution};
+
+fn elevation(val: char) -> u32 {
+    match val {
+        'a'..='z' => val as u32,
+        'S' => 'a' as u32,
+        'E' => 'z' as u32,
+        _ => panic!("unexpected val"),
+    }
+}
+
+fn build_graph(grid: &Grid<char>) -> Graph<Position> {
+    let mut adjacencies = Graph::new();
+
+    for (position, val) in grid.iter() {
+        adjacencies.add_node(position);
+        for neighbour in grid.neighbours4(position) {
+            if elevation(grid[neighbour]) <= elevation(*val) + 1 {
+                adjacencies.add_edge(position, neighbour, 1);
+            }
+        }
+    }
+    adjacencies
+}
+
+fn find_char_coords(ch: char, grid: &Grid<char>) -> Vec<Position> {
+    grid.positions_where(|val| *val == ch)
+}
+
+// Every square visited, from the start to the end. Displays as the number of steps taken.
+pub struct Route(pub Vec<Position>);
+
+impl Route {
+    pub fn steps(&self) -> usize {
+        self.0.len() - 1
+    }
+}
+
+impl Display for Route {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.steps())
+    }
+}
+
+// None if E can't be reached, like a heightmap with a cliff all the way round it. In part 2 that's
+// from any square at elevation a.
+pub struct ShortestRoute(pub Option<Route>);
+
+impl Display for ShortestRoute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(route) => write!(f, "{route}"),
+            None => write!(f, "none, since E can't be reached"),
+        }
+    }
+}
+
+// Draws the route like the puzzle text does: an arrow on each square pointing to the next one, E
+// at the end and . everywhere else.
+pub fn render_route(grid: &Grid<char>, route: &Route) -> String {
+    let mut arrows = grid.map(|_| '.');
+    for (&(x, y), &(next_x, next_y)) in route.0.iter().zip(route.0.iter().skip(1)) {
+        arrows[(x, y)] = match (next_x.cmp(&x), next_y.cmp(&y)) {
+            (_, Ordering::Less) => '^',
+            (_, Ordering::Greater) => 'v',
+            (Ordering::Less, _) => '<',
+            _ => '>',
+        };
+    }
+    if let Some(&end) = route.0.last() {
+        arrows[end] = 'E';
+    }
+    arrows.to_string()
+}
+
+fn parse_heightmap(input: &str) -> Result<Grid<char>, ParseError> {
+    let grid = Grid::parse(input, |line, found, ch| match ch {
+        'a'..='z' | 'S' | 'E' => Ok(ch),
+        _ => Err(line.error(found, "a-z, S or E")),
+    })?;
+    for (ch, name) in [('S', "start"), ('E', "end")] {
+        let count = find_char_coords(ch, &grid).len();
+        if count != 1 {
+            return Err(ParseError::invalid_input(
+                format!("exactly one {name} square marked {ch}"),
+                count.to_string(),
+            ));
+        }
+    }
+    Ok(grid)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    const INPUT_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Grid<char>;
+    type Part1 = ShortestRoute;
+    type Part2 = ShortestRoute;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_heightmap(input)
+    }
+
+    fn part1(grid: &Self::Input) -> ShortestRoute {
+        let start = find_char_coords('S', grid)[0];
+        let end = find_char_coords('E', grid)[0];
+        let graph = build_graph(grid);
+        // Every step is at most one square closer to the end.
+        let distance_to_end = |&(x, y): &Position| (x.abs_diff(end.0) + y.abs_diff(end.1)) as u32;
+        let route = graph.a_star(&start, &end, distance_to_end);
+        ShortestRoute(route.map(|(_, path)| Route(path)))
+    }
+
+    fn part2(grid: &Self::Input) -> ShortestRoute {
+        let end = find_char_coords('E', grid)[0];
+        let graph = build_graph(grid);
+
+        // Searching backwards from the end finds the way from every square in one go.
+        let paths = graph.reverse_bfs_paths([end]);
+        let lowest_squares = grid.positions_where(|val| elevation(*val) == elevation('a'));
+        let start = lowest_squares
+            .into_iter()
+            .filter_map(|start| Some((paths.distance(&start)?, start)))
+            .min();
+        ShortestRoute(start.and_then(|(_, start)| paths.path(&start)).map(Route))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::{check_answers, read_input, InputSource, Solution};
+
+    use super::{render_route, Day12};
+
+    #[test]
+    fn example() {
+        check_answers::<Day12>("example");
+    }
+
+    #[test]
+    fn input() {
+        check_answers::<Day12>("input");
+    }
+
+    #[test]
+    fn example_route() {
+        let input = read_input(
+            Day12::INPUT_DIR,
+            &InputSource::Variant("example".to_owned()),
+        );
+        let grid = Day12::parse(&input.unwrap()).unwrap();
+        let route = Day12::part1(&grid).0.unwrap();
+        assert_eq!(route.0.first(), Some(&(0, 0)));
+        assert_eq!(route.0.last(), Some(&(5, 2)));
+        // Several routes are equally short, so only check the shape of the rendering.
+        let rendered = render_route(&grid, &route);
+        assert_eq!(rendered.lines().count(), 5);
+        assert_eq!(
+            rendered.chars().filter(|ch| "^v<>".contains(*ch)).count(),
+            31
+        );
+    }
+
+    #[test]
+    fn unreachable_end() {
+        let grid = Day12::parse("SzE\n").unwrap();
+        assert!(Day12::part1(&grid).0.is_none());
+        assert!(Day12::part2(&grid).0.is_none());
+        assert_eq!(
+            Day12::part1(&grid).to_string(),
+            "none, since E can't be reached"
+        );
+    }
+
+    #[test]
+    fn needs_one_start_and_one_end() {
+        let err = Day12::parse("SabE\nSbcd\n").err().unwrap();
+        assert_eq!(err.line, 0);
+        assert_eq!(err.found, "2");
+        assert_eq!(err.expected, "exactly one start square marked S");
+
+        let err = Day12::parse("Sabc\n").err().unwrap();
+        assert_eq!(err.found, "0");
+        assert_eq!(err.expected, "exactly one end square marked E");
+    }
+}
@@ -0,0 +1,217 @@
+use std::{cmp::Ordering, iter::Peekable, str::CharIndices};
+
+use itertools::{EitherOrBoth, Itertools};
+use utils::{numbered_lines, Line, ParseError, Solution};
+
+#[derive(Clone, Debug)]
+enum Item {
+    List(Vec<Item>),
+    Num(u32),
+}
+
+#[derive(Clone)]
+pub struct Packet(Vec<Item>);
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        packet_ordering(&self.0, &other.0).is_eq()
+    }
+}
+
+impl Eq for Packet {}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        packet_ordering(&self.0, &other.0)
+    }
+}
+
+fn items_are_correctly_ordered(left_item: &Item, right_item: &Item) -> Ordering {
+    match left_item {
+        Item::Num(left_num) => match right_item {
+            Item::Num(right_num) => match left_num.cmp(right_num) {
+                Ordering::Less => Ordering::Less,
+                Ordering::Greater => Ordering::Greater,
+                Ordering::Equal => Ordering::Equal,
+            },
+            Item::List(right_vec) => packet_ordering(std::slice::from_ref(left_item), right_vec),
+        },
+        Item::List(left_vec) => match right_item {
+            Item::Num(right_num) => packet_ordering(left_vec, &[Item::Num(*right_num)]),
+            Item::List(right_vec) => packet_ordering(left_vec, right_vec),
+        },
+    }
+}
+
+fn packet_ordering(left_packet: &[Item], right_packet: &[Item]) -> Ordering {
+    for either_or_both in left_packet.iter().zip_longest(right_packet) {
+        match either_or_both {
+            EitherOrBoth::Right(_) => return Ordering::Less,
+            EitherOrBoth::Left(_) => return Ordering::Greater,
+            EitherOrBoth::Both(left_item, right_item) => {
+                let item_ordering = items_are_correctly_ordered(left_item, right_item);
+                if item_ordering != Ordering::Equal {
+                    return item_ordering;
+                }
+            }
+        }
+    }
+    Ordering::Equal
+}
+
+type PacketChars<'a> = Peekable<CharIndices<'a>>;
+
+fn unexpected(line: &Line, packet: &mut PacketChars, expected: &str) -> ParseError {
+    match packet.peek() {
+        Some(&(idx, ch)) => line.error(&line.text[idx..idx + ch.len_utf8()], expected),
+        None => line.error_at_end(expected),
+    }
+}
+
+fn maybe_take_digit(packet: &mut PacketChars) -> Option<char> {
+    packet
+        .next_if(|(_, ch)| ch.is_ascii_digit())
+        .map(|(_, ch)| ch)
+}
+
+fn take_num(line: &Line, packet: &mut PacketChars) -> Result<u32, ParseError> {
+    let start = packet.peek().map_or(line.text.len(), |(idx, _)| *idx);
+    let mut result = String::new();
+    while let Some(digit) = maybe_take_digit(packet) {
+        result.push(digit);
+    }
+    let end = start + result.len();
+    line.parse(&line.text[start..end], "a number")
+}
+
+fn maybe_take_comma(packet: &mut PacketChars) -> bool {
+    packet.next_if(|(_, ch)| *ch == ',').is_some()
+}
+
+fn take_char(
+    line: &Line,
+    packet: &mut PacketChars,
+    expected_char: char,
+    expected: &str,
+) -> Result<(), ParseError> {
+    match packet.next_if(|(_, ch)| *ch == expected_char) {
+        Some(_) => Ok(()),
+        None => Err(unexpected(line, packet, expected)),
+    }
+}
+
+fn take_vec(line: &Line, packet: &mut PacketChars) -> Result<Vec<Item>, ParseError> {
+    take_char(line, packet, '[', "[")?;
+    let mut result = vec![];
+    if let Some(first_item) = maybe_take_item(line, packet)? {
+        result.push(first_item);
+    }
+    while maybe_take_comma(packet) {
+        let item = maybe_take_item(line, packet)?
+            .ok_or_else(|| unexpected(line, packet, "a number or a list"))?;
+        result.push(item);
+    }
+    take_char(line, packet, ']', ", or ]")?;
+    Ok(result)
+}
+
+fn maybe_take_item(line: &Line, packet: &mut PacketChars) -> Result<Option<Item>, ParseError> {
+    let next_char = packet.peek().map(|(_, ch)| *ch);
+    Ok(match next_char {
+        Some('0'..='9') => Some(Item::Num(take_num(line, packet)?)),
+        Some('[') => Some(Item::List(take_vec(line, packet)?)),
+        _ => None,
+    })
+}
+
+fn parse_packet(line: Line) -> Result<Packet, ParseError> {
+    let mut peekable_chars = line.text.char_indices().peekable();
+    let packet = Packet(take_vec(&line, &mut peekable_chars)?);
+    if peekable_chars.peek().is_some() {
+        return Err(unexpected(&line, &mut peekable_chars, "end of line"));
+    }
+    Ok(packet)
+}
+
+fn divider_packet(num: u32) -> Packet {
+    Packet(vec![Item::List(vec![Item::Num(num)])])
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    const INPUT_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Vec<Packet>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let packets: Vec<_> = numbered_lines(input)
+            .filter(|line| !line.text.is_empty())
+            .map(parse_packet)
+            .collect::<Result<_, _>>()?;
+        if packets.len() % 2 != 0 {
+            return Err(ParseError::end_of_input(
+                "another packet to complete the last pair",
+            ));
+        }
+        Ok(packets)
+    }
+
+    fn part1(packets: &Self::Input) -> usize {
+        let packet_pairs = packets.chunks(2);
+        packet_pairs
+            .into_iter()
+            .map(|chunk| packet_ordering(&chunk[0].0, &chunk[1].0))
+            .enumerate()
+            .filter_map(|(pair_idx, is_correctly_ordered)| {
+                (is_correctly_ordered == Ordering::Less).then_some(pair_idx + 1)
+            })
+            .sum()
+    }
+
+    fn part2(packets: &Self::Input) -> usize {
+        let mut packets = packets.clone();
+        let marker_packet_1 = divider_packet(2);
+        let marker_packet_2 = divider_packet(6);
+        packets.push(marker_packet_1.clone());
+        packets.push(marker_packet_2.clone());
+
+        packets.sort();
+
+        let marker_1_position = packets
+            .iter()
+            .find_position(|packet| packet == &&marker_packet_1);
+
+        let marker_2_position = packets
+            .iter()
+            .find_position(|packet| packet == &&marker_packet_2);
+
+        (marker_1_position.unwrap().0 + 1) * (marker_2_position.unwrap().0 + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::check_answers;
+
+    use super::Day13;
+
+    #[test]
+    fn example() {
+        check_answers::<Day13>("example");
+    }
+
+    #[test]
+    fn input() {
+        check_answers::<Day13>("input");
+    }
+}
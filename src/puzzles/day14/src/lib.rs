@@ -0,0 +1,183 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use utils::{numbered_lines, Grid, Line, ParseError, Solution};
+
+fn add_vertical_section(x: i32, y1: i32, y2: i32, rocks: &mut HashSet<(i32, i32)>) {
+    for y in y1..=y2 {
+        rocks.insert((x, y));
+    }
+}
+
+fn add_horizontal_section(y: i32, x1: i32, x2: i32, rocks: &mut HashSet<(i32, i32)>) {
+    for x in x1..=x2 {
+        rocks.insert((x, y));
+    }
+}
+
+fn parse_point(line: &Line, section: &str) -> Result<(i32, i32), ParseError> {
+    let (x, y) = section
+        .split_once(',')
+        .ok_or_else(|| line.error(section, "a point like 498,4"))?;
+    let x = line.parse(x, "an x coordinate")?;
+    // The sand source is at y=0, and nothing can be above it.
+    match line.parse(y, "a y coordinate")? {
+        y_value @ 0.. => Ok((x, y_value)),
+        _ => Err(line.error(y, "a y coordinate of at least 0")),
+    }
+}
+
+fn get_rock_positions(input: &str) -> Result<HashSet<(i32, i32)>, ParseError> {
+    let mut rocks = HashSet::new();
+    for line in numbered_lines(input) {
+        let sections: Vec<_> = line.text.split(" -> ").collect();
+        let points = sections
+            .iter()
+            .map(|section| parse_point(&line, section))
+            .collect::<Result<Vec<_>, _>>()?;
+        for (idx, (section_start, section_end)) in points.into_iter().tuple_windows().enumerate() {
+            if section_start.0 == section_end.0 {
+                add_vertical_section(
+                    section_start.0,
+                    i32::min(section_start.1, section_end.1),
+                    i32::max(section_start.1, section_end.1),
+                    &mut rocks,
+                );
+            } else if section_start.1 == section_end.1 {
+                add_horizontal_section(
+                    section_start.1,
+                    i32::min(section_start.0, section_end.0),
+                    i32::max(section_start.0, section_end.0),
+                    &mut rocks,
+                );
+            } else {
+                return Err(line.error(
+                    sections[idx + 1],
+                    "a point in line with the previous one, as sections must be vertical or horizontal",
+                ));
+            }
+        }
+    }
+    if rocks.is_empty() {
+        return Err(ParseError::end_of_input("at least one rock path"));
+    }
+    Ok(rocks)
+}
+
+const SAND_SOURCE_X: i32 = 500;
+
+pub struct Cave {
+    // Indexed by (x - x_offset, y). The bottom row is the one just above the floor, where sand
+    // either settles or, without a floor, falls into the abyss.
+    rocks: Grid<bool>,
+    x_offset: i32,
+}
+
+impl Cave {
+    fn new(rock_positions: HashSet<(i32, i32)>) -> Self {
+        let rocks_max_y = rock_positions.iter().map(|(_, y)| *y).max().unwrap();
+        let height = rocks_max_y + 2;
+        // Sand moves at most one column sideways for each row it falls.
+        let min_x = rock_positions
+            .iter()
+            .map(|(x, _)| *x)
+            .chain([SAND_SOURCE_X - height])
+            .min()
+            .unwrap();
+        let max_x = rock_positions
+            .iter()
+            .map(|(x, _)| *x)
+            .chain([SAND_SOURCE_X + height])
+            .max()
+            .unwrap();
+        let mut rocks = Grid::new((max_x - min_x + 1) as usize, height as usize, false);
+        for (x, y) in rock_positions {
+            rocks[((x - min_x) as usize, y as usize)] = true;
+        }
+        Self {
+            rocks,
+            x_offset: min_x,
+        }
+    }
+}
+
+fn count_settled_grains(cave: &Cave, include_floor: bool) -> usize {
+    let mut blocked = cave.rocks.clone();
+    let source = ((SAND_SOURCE_X - cave.x_offset) as usize, 0);
+    let lowest_y = blocked.height() - 1;
+    let mut settled_sand_grains = 0;
+
+    loop {
+        let (mut x, mut y) = source;
+        loop {
+            if y == lowest_y {
+                if !include_floor {
+                    return settled_sand_grains;
+                }
+                break;
+            }
+            match [x, x - 1, x + 1]
+                .into_iter()
+                .find(|next_x| !blocked[(*next_x, y + 1)])
+            {
+                Some(next_x) => (x, y) = (next_x, y + 1),
+                None => break,
+            }
+        }
+        blocked[(x, y)] = true;
+        settled_sand_grains += 1;
+
+        if (x, y) == source {
+            return settled_sand_grains;
+        }
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    const INPUT_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Cave;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        get_rock_positions(input).map(Cave::new)
+    }
+
+    fn part1(cave: &Self::Input) -> usize {
+        count_settled_grains(cave, false)
+    }
+
+    fn part2(cave: &Self::Input) -> usize {
+        count_settled_grains(cave, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::{check_answers, Solution};
+
+    use super::Day14;
+
+    #[test]
+    fn example() {
+        check_answers::<Day14>("example");
+    }
+
+    #[test]
+    fn input() {
+        check_answers::<Day14>("input");
+    }
+
+    #[test]
+    fn rocks_above_the_sand_source() {
+        let err = Day14::parse("498,4 -> 498,6\n498,-4 -> 498,6\n")
+            .err()
+            .unwrap();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 5, "-4"));
+        assert_eq!(err.expected, "a y coordinate of at least 0");
+    }
+}
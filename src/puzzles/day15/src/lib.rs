@@ -0,0 +1,378 @@
+mod render;
+
+use std::fmt::{self, Display};
+
+use gcollections::ops::{Bounded, Cardinality, Difference, Empty, Union};
+use interval::{interval_set::ToIntervalSet, IntervalSet};
+pub use render::{render_ascii, render_ppm, Viewport};
+use utils::{numbered_lines, parse_lines, Line, ParseError, Solution};
+
+#[derive(Debug)]
+struct Coordinates {
+    x: i64,
+    y: i64,
+}
+
+impl Coordinates {
+    fn manhattan_distance(&self, other: &Self) -> i64 {
+        i64::abs(self.x - other.x) + i64::abs(self.y - other.y)
+    }
+}
+
+#[derive(Debug)]
+pub struct Sensor {
+    coordinates: Coordinates,
+    closest_beacon: Coordinates,
+}
+
+fn remove(interval_set: IntervalSet<i64>, element: i64) -> IntervalSet<i64> {
+    interval_set.difference(&vec![(element, element)].to_interval_set())
+}
+
+impl Sensor {
+    fn radius(&self) -> i64 {
+        self.coordinates.manhattan_distance(&self.closest_beacon)
+    }
+
+    fn covers(&self, position: &Coordinates) -> bool {
+        self.coordinates.manhattan_distance(position) <= self.radius()
+    }
+
+    fn area_where_beacon_isnt(
+        &self,
+        y: i64,
+        exclude_position_closest_beacon: bool,
+    ) -> IntervalSet<i64> {
+        let manhattan_distance = self.radius();
+        let vertical_distance = i64::abs(self.coordinates.y - y);
+        if vertical_distance > manhattan_distance {
+            IntervalSet::empty()
+        } else {
+            let horizontal_distance = manhattan_distance - vertical_distance;
+            let start = self.coordinates.x - horizontal_distance;
+            let end = self.coordinates.x + horizontal_distance;
+            if y == self.closest_beacon.y && exclude_position_closest_beacon {
+                remove(vec![(start, end)].to_interval_set(), self.closest_beacon.x)
+            } else {
+                vec![(start, end)].to_interval_set()
+            }
+        }
+    }
+}
+
+fn parse_coordinate(
+    line: &Line,
+    token: &str,
+    prefix: &str,
+    suffix: &str,
+) -> Result<i64, ParseError> {
+    let value = line.strip_prefix(token, prefix)?;
+    let value = line.strip_suffix(value, suffix)?;
+    line.parse(value, "a coordinate")
+}
+
+fn parse_sensor(line: Line) -> Result<Sensor, ParseError> {
+    let parts = line.tokens();
+    let sensor_x = parse_coordinate(&line, parts.get(2, "the sensor's x=")?, "x=", ",")?;
+    let sensor_y = parse_coordinate(&line, parts.get(3, "the sensor's y=")?, "y=", ":")?;
+    let beacon_x = parse_coordinate(&line, parts.get(8, "the beacon's x=")?, "x=", ",")?;
+    let beacon_y = parse_coordinate(&line, parts.get(9, "the beacon's y=")?, "y=", "")?;
+    Ok(Sensor {
+        coordinates: Coordinates {
+            x: sensor_x,
+            y: sensor_y,
+        },
+        closest_beacon: Coordinates {
+            x: beacon_x,
+            y: beacon_y,
+        },
+    })
+}
+
+fn combined_area_where_beacon_isnt(
+    sensors: &[Sensor],
+    y: i64,
+    exclude_position_closest_beacon: bool,
+) -> IntervalSet<i64> {
+    sensors.iter().fold(IntervalSet::empty(), |acc, sensor| {
+        let exclusion_zone = sensor.area_where_beacon_isnt(y, exclude_position_closest_beacon);
+        acc.union(&exclusion_zone)
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BeaconSearch {
+    // The row part 1 counts the positions where a beacon can't be.
+    pub target_row: i64,
+    // The distress beacon has x and y between 0 and this, inclusive.
+    pub search_limit: i64,
+    pub tuning_multiplier: i64,
+}
+
+impl BeaconSearch {
+    pub const PUZZLE: Self = Self {
+        target_row: 2000000,
+        search_limit: 4000000,
+        tuning_multiplier: 4000000,
+    };
+
+    // One setting per line, "row: 10", "search limit: 20" or "tuning multiplier: 4000000", e.g.
+    // for the example in the puzzle text, which is much smaller. Anything left out keeps the real
+    // puzzle's value.
+    fn parse_settings(settings: &str) -> Result<Self, ParseError> {
+        let mut search = Self::PUZZLE;
+        for line in numbered_lines(settings) {
+            if line.text.trim().is_empty() {
+                continue;
+            }
+            let Some((name, value)) = line.text.split_once(':') else {
+                return Err(line.error_at_end("a setting like \"row: 10\""));
+            };
+            let value = line.parse(value.trim(), "a number")?;
+            match name.trim() {
+                "row" => search.target_row = value,
+                "search limit" => search.search_limit = value,
+                "tuning multiplier" => search.tuning_multiplier = value,
+                _ => return Err(line.error(name, "row, search limit or tuning multiplier")),
+            }
+        }
+        Ok(search)
+    }
+}
+
+impl Default for BeaconSearch {
+    fn default() -> Self {
+        Self::PUZZLE
+    }
+}
+
+pub struct Report {
+    pub sensors: Vec<Sensor>,
+    // From the input's settings, but can be overridden.
+    pub search: BeaconSearch,
+}
+
+// In coordinates rotated by 45 degrees, a = x + y and b = x - y, each sensor covers a square. The
+// distress beacon is the only uncovered cell in the search area, so unless it's against the edge
+// of the area it's boxed in by sensors, and sits where a line just outside one square's edge
+// crosses a line just outside another's. Against the edge, it's where one of those lines meets the
+// edge.
+pub fn find_distress_beacon(sensors: &[Sensor], search_limit: i64) -> Option<(i64, i64)> {
+    let mut a_lines = vec![];
+    let mut b_lines = vec![];
+    for sensor in sensors {
+        let Coordinates { x, y } = sensor.coordinates;
+        let distance = sensor.radius() + 1;
+        a_lines.extend([x + y - distance, x + y + distance]);
+        b_lines.extend([x - y - distance, x - y + distance]);
+    }
+    a_lines.sort();
+    a_lines.dedup();
+    b_lines.sort();
+    b_lines.dedup();
+
+    let crossings = a_lines.iter().flat_map(|a| {
+        b_lines
+            .iter()
+            .filter(move |b| (a + *b) % 2 == 0)
+            .map(move |b| ((a + b) / 2, (a - b) / 2))
+    });
+    let edges = [0, search_limit];
+    let on_edges = edges.into_iter().flat_map(|edge| {
+        let from_a_lines = a_lines
+            .iter()
+            .flat_map(move |a| [(edge, a - edge), (a - edge, edge)]);
+        let from_b_lines = b_lines
+            .iter()
+            .flat_map(move |b| [(edge, edge - b), (b + edge, edge)]);
+        from_a_lines.chain(from_b_lines)
+    });
+    let corners = edges
+        .into_iter()
+        .flat_map(|x| edges.into_iter().map(move |y| (x, y)));
+
+    crossings.chain(on_edges).chain(corners).find(|&(x, y)| {
+        let in_area = (0..=search_limit).contains(&x) && (0..=search_limit).contains(&y);
+        in_area
+            && !sensors
+                .iter()
+                .any(|sensor| sensor.covers(&Coordinates { x, y }))
+    })
+}
+
+// Every cell in the search area that no sensor covers, row by row. Beacons count as covered.
+pub fn uncovered_cells(sensors: &[Sensor], search_limit: i64) -> Vec<(i64, i64)> {
+    let all_col_idxs = vec![(0, search_limit)].to_interval_set();
+    (0..=search_limit)
+        .flat_map(|y| {
+            let uncovered =
+                all_col_idxs.difference(&combined_area_where_beacon_isnt(sensors, y, false));
+            uncovered
+                .into_iter()
+                .flat_map(|interval| interval.lower()..=interval.upper())
+                .map(move |x| (x, y))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+pub fn tuning_frequency(sensors: &[Sensor], search: &BeaconSearch) -> Option<i64> {
+    let (x, y) = find_distress_beacon(sensors, search.search_limit)?;
+    Some(x * search.tuning_multiplier + y)
+}
+
+// None if every cell in the search area is covered, so there's nowhere for the distress beacon.
+pub struct TuningFrequency(pub Option<i64>);
+
+impl Display for TuningFrequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(frequency) => write!(f, "{frequency}"),
+            None => write!(f, "every cell in the search area is covered"),
+        }
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    const INPUT_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Report;
+    type Part1 = u64;
+    type Part2 = TuningFrequency;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with_settings(input, "")
+    }
+
+    fn parse_with_settings(input: &str, settings: &str) -> Result<Self::Input, ParseError> {
+        let sensors = parse_lines(input, parse_sensor)?;
+        let search = BeaconSearch::parse_settings(settings)?;
+        Ok(Report { sensors, search })
+    }
+
+    fn part1(report: &Self::Input) -> u64 {
+        combined_area_where_beacon_isnt(&report.sensors, report.search.target_row, true).size()
+    }
+
+    fn part2(report: &Self::Input) -> TuningFrequency {
+        TuningFrequency(tuning_frequency(&report.sensors, &report.search))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::{check_answers, read_input, read_settings, InputSource, Solution};
+
+    use super::{
+        render_ascii, uncovered_cells, BeaconSearch, Coordinates, Day15, Sensor, Viewport,
+    };
+
+    // Covers the cells at most 2 away from (2, 2), from (2, 0) at the top to (2, 4) at the bottom.
+    fn single_sensor() -> Vec<Sensor> {
+        vec![Sensor {
+            coordinates: Coordinates { x: 2, y: 2 },
+            closest_beacon: Coordinates { x: 2, y: 4 },
+        }]
+    }
+
+    #[test]
+    fn example() {
+        check_answers::<Day15>("example");
+    }
+
+    #[test]
+    fn input() {
+        check_answers::<Day15>("input");
+    }
+
+    #[test]
+    fn example_settings_are_read() {
+        let source = InputSource::Variant("example".to_owned());
+        let input = read_input(Day15::INPUT_DIR, &source).unwrap();
+        let settings = read_settings(Day15::INPUT_DIR, &source).unwrap();
+        let report = Day15::parse_with_settings(&input, &settings).unwrap();
+        let example = BeaconSearch {
+            target_row: 10,
+            search_limit: 20,
+            ..BeaconSearch::PUZZLE
+        };
+        assert_eq!(report.search, example);
+        // The distress beacon is the only uncovered cell in the example's search area.
+        assert_eq!(uncovered_cells(&report.sensors, 20), vec![(14, 11)]);
+
+        let rendered = render_ascii(&report.sensors, &Viewport::search_area(20, 100));
+        let uncovered: Vec<_> = rendered
+            .lines()
+            .enumerate()
+            .flat_map(|(y, row)| row.match_indices('.').map(move |(x, _)| (x, y)))
+            .collect();
+        assert_eq!(uncovered, vec![(14, 11)]);
+        assert_eq!(rendered.matches('S').count(), 14);
+    }
+
+    #[test]
+    fn malformed_sensors() {
+        let line = "Sensor at x=2, y=18: closest beacon is at x=-2 y=15";
+        let err = Day15::parse(line).err().unwrap();
+        assert_eq!((err.line, err.column, err.found.as_str()), (1, 45, "-2"));
+        assert_eq!(err.expected, "text ending with \",\"");
+
+        let line = "Sensor at x=2, y=eighteen: closest beacon is at x=-2, y=15";
+        let err = Day15::parse(line).err().unwrap();
+        assert_eq!(
+            (err.line, err.column, err.found.as_str()),
+            (1, 18, "eighteen")
+        );
+        assert_eq!(err.expected, "a coordinate");
+
+        let err = Day15::parse("Sensor at x=2, y=18: closest beacon")
+            .err()
+            .unwrap();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 36, "the beacon's x=")
+        );
+    }
+
+    #[test]
+    fn unknown_setting_is_rejected() {
+        let err = Day15::parse_with_settings("", "row: 10\nsearch area: 20\n").err();
+        let err = err.unwrap();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.found, "search area");
+        assert_eq!(err.expected, "row, search limit or tuning multiplier");
+    }
+
+    #[test]
+    fn single_sensor_covers_its_tips() {
+        // The uncovered cells are marked with #.
+        let picture = "\
+##.##
+#...#
+.....
+#...#
+##.##
+";
+        let expected: Vec<_> = (0..)
+            .zip(picture.lines())
+            .flat_map(|(y, row)| row.match_indices('#').map(move |(x, _)| (x as i64, y)))
+            .collect();
+        assert_eq!(uncovered_cells(&single_sensor(), 4), expected);
+    }
+
+    #[test]
+    fn single_sensor_is_drawn_as_a_full_diamond() {
+        let viewport = Viewport::parse("0,0,4,4", 1).unwrap();
+        let expected = "\
+..#..
+.###.
+##S##
+.###.
+..B..
+";
+        assert_eq!(render_ascii(&single_sensor(), &viewport), expected);
+    }
+}
@@ -1,116 +1,122 @@
-use gcollections::ops::{Bounded, Cardinality, Difference, Empty, Union};
-use interval::{interval_set::ToIntervalSet, IntervalSet};
-use utils::read_input;
+use std::{env, fs};
 
-#[derive(Debug)]
-struct Coordinates {
-    x: i64,
-    y: i64,
+use day15::{render_ascii, render_ppm, uncovered_cells, BeaconSearch, Day15, Report, Viewport};
+use utils::{exit_with_error, take_flag, take_number, take_value, Solution};
+
+// Search settings given on the command line, replacing the ones from the input's settings file.
+struct SearchOverrides {
+    target_row: Option<i64>,
+    search_limit: Option<i64>,
+    tuning_multiplier: Option<i64>,
 }
 
-impl Coordinates {
-    fn manhattan_distance(&self, other: &Self) -> i64 {
-        i64::abs(self.x - other.x) + i64::abs(self.y - other.y)
+impl SearchOverrides {
+    fn take(args: &mut Vec<String>) -> Result<Self, String> {
+        Ok(Self {
+            target_row: take_number(args, "--row")?,
+            search_limit: take_number(args, "--search-limit")?,
+            tuning_multiplier: take_number(args, "--tuning-multiplier")?,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.target_row.is_none() && self.search_limit.is_none() && self.tuning_multiplier.is_none()
+    }
+
+    fn apply(&self, search: &mut BeaconSearch) {
+        search.target_row = self.target_row.unwrap_or(search.target_row);
+        search.search_limit = self.search_limit.unwrap_or(search.search_limit);
+        search.tuning_multiplier = self.tuning_multiplier.unwrap_or(search.tuning_multiplier);
     }
 }
 
-#[derive(Debug)]
-struct Sensor {
-    coordinates: Coordinates,
-    closest_beacon: Coordinates,
+enum Render {
+    Ascii,
+    Ppm(String),
 }
 
-fn remove(interval_set: IntervalSet<i64>, element: i64) -> IntervalSet<i64> {
-    interval_set.difference(&vec![(element, element)].to_interval_set())
+struct RenderOptions {
+    render: Render,
+    viewport: Option<String>,
+    scale: Option<i64>,
 }
 
-impl Sensor {
-    fn area_where_beacon_isnt(
-        &self,
-        y: i64,
-        exclude_position_closest_beacon: bool,
-    ) -> IntervalSet<i64> {
-        let manhattan_distance = self.coordinates.manhattan_distance(&self.closest_beacon);
-        let vertical_distance = i64::abs(self.coordinates.y - y);
-        if vertical_distance >= manhattan_distance {
-            IntervalSet::empty()
-        } else {
-            let horizontal_distance = manhattan_distance - vertical_distance;
-            let start = self.coordinates.x - horizontal_distance;
-            let end = self.coordinates.x + horizontal_distance;
-            if y == self.closest_beacon.y && exclude_position_closest_beacon {
-                remove(vec![(start, end)].to_interval_set(), self.closest_beacon.x)
-            } else {
-                vec![(start, end)].to_interval_set()
-            }
+impl RenderOptions {
+    // Returns None unless --ascii or --ppm was given.
+    fn take(args: &mut Vec<String>) -> Result<Option<Self>, String> {
+        let render = match (take_flag(args, "--ascii"), take_value(args, "--ppm")?) {
+            (false, None) => return Ok(None),
+            (true, None) => Render::Ascii,
+            (false, Some(path)) => Render::Ppm(path),
+            (true, Some(_)) => return Err("expected only one of --ascii and --ppm".to_owned()),
+        };
+        let scale = take_number(args, "--scale")?;
+        if let Some(scale @ ..=0) = scale {
+            return Err(format!("expected a scale of at least 1, found {scale}"));
         }
+        Ok(Some(Self {
+            render,
+            viewport: take_value(args, "--viewport")?,
+            scale,
+        }))
     }
-}
 
-fn parse_sensor(line: &str) -> Sensor {
-    let parts: Vec<_> = line.split(' ').collect();
-    let sensor_x = parts[2]
-        .strip_prefix("x=")
-        .unwrap()
-        .strip_suffix(',')
-        .unwrap()
-        .parse::<i64>()
-        .unwrap();
-    let sensor_y = parts[3]
-        .strip_prefix("y=")
-        .unwrap()
-        .strip_suffix(':')
-        .unwrap()
-        .parse::<i64>()
-        .unwrap();
-    let beacon_x = parts[8]
-        .strip_prefix("x=")
-        .unwrap()
-        .strip_suffix(',')
-        .unwrap()
-        .parse::<i64>()
-        .unwrap();
-    let beacon_y = parts[9].strip_prefix("y=").unwrap().parse::<i64>().unwrap();
-    Sensor {
-        coordinates: Coordinates {
-            x: sensor_x,
-            y: sensor_y,
-        },
-        closest_beacon: Coordinates {
-            x: beacon_x,
-            y: beacon_y,
-        },
+    // Without a --viewport, draws the whole search area at a size that fits a terminal or a
+    // screen.
+    fn viewport(&self, report: &Report) -> Result<Viewport, String> {
+        match &self.viewport {
+            Some(bounds) => Viewport::parse(bounds, self.scale.unwrap_or(1)),
+            None => {
+                let max_size = match self.render {
+                    Render::Ascii => 100,
+                    Render::Ppm(_) => 1000,
+                };
+                let mut viewport = Viewport::search_area(report.search.search_limit, max_size);
+                viewport.scale = self.scale.unwrap_or(viewport.scale);
+                Ok(viewport)
+            }
+        }
     }
-}
 
-fn combined_area_where_beacon_isnt(
-    sensors: &[Sensor],
-    y: i64,
-    exclude_position_closest_beacon: bool,
-) -> IntervalSet<i64> {
-    sensors.iter().fold(IntervalSet::empty(), |acc, sensor| {
-        let exclusion_zone = sensor.area_where_beacon_isnt(y, exclude_position_closest_beacon);
-        acc.union(&exclusion_zone)
-    })
+    fn render(&self, report: &Report) -> Result<(), String> {
+        let viewport = self.viewport(report)?;
+        match &self.render {
+            Render::Ascii => print!("{}", render_ascii(&report.sensors, &viewport)),
+            Render::Ppm(path) => fs::write(path, render_ppm(&report.sensors, &viewport))
+                .map_err(|err| format!("failed to write {path}: {err}"))?,
+        }
+        Ok(())
+    }
 }
 
 fn main() {
-    let input = read_input();
-    let sensors: Vec<_> = input.lines().map(parse_sensor).collect();
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let list_uncovered = take_flag(&mut args, "--uncovered");
+    let overrides =
+        SearchOverrides::take(&mut args).unwrap_or_else(|message| exit_with_error(message));
+    let render_options =
+        RenderOptions::take(&mut args).unwrap_or_else(|message| exit_with_error(message));
+    if !list_uncovered && overrides.is_empty() && render_options.is_none() {
+        return utils::run_with_args::<Day15>(args.into_iter());
+    }
 
-    let part_1_answer = combined_area_where_beacon_isnt(&sensors, 2000000, true).size();
-    println!("part 1: {}", part_1_answer);
+    let mut report = utils::load_input::<Day15>(args.into_iter());
+    overrides.apply(&mut report.search);
 
-    // part 2 - this is kind of a  stupid slow way of doing it...but it still only takes a few seconds (when compiled with --release)
-    let search_limit = 4000000;
-    let all_col_idxs = vec![(0, search_limit)].to_interval_set();
+    if let Some(render_options) = render_options {
+        return render_options
+            .render(&report)
+            .unwrap_or_else(|message| exit_with_error(message));
+    }
 
-    for y in 0..search_limit {
-        let possible_locations =
-            all_col_idxs.difference(&combined_area_where_beacon_isnt(&sensors, y, false));
-        if let Some(x) = possible_locations.into_iter().next() {
-            println!("part 2: {}", x.lower() * 4000000 + y);
-            break;
+    if list_uncovered {
+        let cells = uncovered_cells(&report.sensors, report.search.search_limit);
+        for (x, y) in &cells {
+            println!("x={x}, y={y}");
         }
+        println!("{} uncovered cells", cells.len());
+        return;
     }
+    println!("part 1: {}", Day15::part1(&report));
+    println!("part 2: {}", Day15::part2(&report));
 }
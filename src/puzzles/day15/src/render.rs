@@ -0,0 +1,155 @@
+use gcollections::ops::{Bounded, Intersection};
+use interval::interval_set::ToIntervalSet;
+
+use crate::{combined_area_where_beacon_isnt, Sensor};
+
+// For positive numbers only. i64::div_ceil isn't stable yet.
+fn div_ceil(numerator: i64, denominator: i64) -> i64 {
+    (numerator + denominator - 1) / denominator
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Viewport {
+    // Inclusive on both ends.
+    pub min_x: i64,
+    pub min_y: i64,
+    pub max_x: i64,
+    pub max_y: i64,
+    // Each character or pixel stands for a square this many cells wide.
+    pub scale: i64,
+}
+
+impl Viewport {
+    // Parses "min_x,min_y,max_x,max_y".
+    pub fn parse(bounds: &str, scale: i64) -> Result<Self, String> {
+        let numbers = bounds
+            .split(',')
+            .map(|number| number.trim().parse())
+            .collect::<Result<Vec<i64>, _>>()
+            .ok()
+            .filter(|numbers| numbers.len() == 4)
+            .ok_or_else(|| format!("expected min_x,min_y,max_x,max_y, found {bounds}"))?;
+        let viewport = Self {
+            min_x: numbers[0],
+            min_y: numbers[1],
+            max_x: numbers[2],
+            max_y: numbers[3],
+            scale,
+        };
+        if viewport.min_x > viewport.max_x || viewport.min_y > viewport.max_y || scale < 1 {
+            return Err(format!("{bounds} at scale {scale} is an empty viewport"));
+        }
+        Ok(viewport)
+    }
+
+    // The square from (0, 0) to (limit, limit), scaled down to at most `max_size` across.
+    pub fn search_area(search_limit: i64, max_size: i64) -> Self {
+        Self {
+            min_x: 0,
+            min_y: 0,
+            max_x: search_limit,
+            max_y: search_limit,
+            scale: div_ceil(search_limit + 1, max_size).max(1),
+        }
+    }
+
+    fn width(&self) -> i64 {
+        div_ceil(self.max_x - self.min_x + 1, self.scale)
+    }
+
+    fn height(&self) -> i64 {
+        div_ceil(self.max_y - self.min_y + 1, self.scale)
+    }
+
+    fn pixel_of(&self, x: i64, y: i64) -> Option<(i64, i64)> {
+        let in_view =
+            (self.min_x..=self.max_x).contains(&x) && (self.min_y..=self.max_y).contains(&y);
+        in_view.then(|| ((x - self.min_x) / self.scale, (y - self.min_y) / self.scale))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Pixel {
+    Sensor,
+    Beacon,
+    // The fraction of the cells sampled for the pixel that some sensor covers.
+    Coverage(f64),
+}
+
+// Each pixel row is sampled along the middle cell row of the square it stands for, which is exact
+// at scale 1 and close enough to see the shape of the coverage when zoomed out.
+fn pixels(sensors: &[Sensor], viewport: &Viewport) -> Vec<Vec<Pixel>> {
+    let mut result: Vec<Vec<Pixel>> = (0..viewport.height())
+        .map(|pixel_y| {
+            let y = i64::min(
+                viewport.min_y + pixel_y * viewport.scale + viewport.scale / 2,
+                viewport.max_y,
+            );
+            let covered = combined_area_where_beacon_isnt(sensors, y, false);
+            (0..viewport.width())
+                .map(|pixel_x| {
+                    let start = viewport.min_x + pixel_x * viewport.scale;
+                    let end = i64::min(start + viewport.scale - 1, viewport.max_x);
+                    let covered_in_pixel =
+                        covered.intersection(&vec![(start, end)].to_interval_set());
+                    let covered_cells: i64 = covered_in_pixel
+                        .iter()
+                        .map(|interval| interval.upper() - interval.lower() + 1)
+                        .sum();
+                    Pixel::Coverage(covered_cells as f64 / (end - start + 1) as f64)
+                })
+                .collect()
+        })
+        .collect();
+
+    for sensor in sensors {
+        let markers = [
+            (&sensor.closest_beacon, Pixel::Beacon),
+            (&sensor.coordinates, Pixel::Sensor),
+        ];
+        for (position, marker) in markers {
+            if let Some((pixel_x, pixel_y)) = viewport.pixel_of(position.x, position.y) {
+                result[pixel_y as usize][pixel_x as usize] = marker;
+            }
+        }
+    }
+    result
+}
+
+// S for sensors and B for beacons as in the puzzle, # where every sampled cell is covered, . where
+// none are, and + where some are.
+pub fn render_ascii(sensors: &[Sensor], viewport: &Viewport) -> String {
+    let mut result = String::new();
+    for row in pixels(sensors, viewport) {
+        for pixel in row {
+            result.push(match pixel {
+                Pixel::Sensor => 'S',
+                Pixel::Beacon => 'B',
+                Pixel::Coverage(fraction) if fraction >= 1.0 => '#',
+                Pixel::Coverage(fraction) if fraction <= 0.0 => '.',
+                Pixel::Coverage(_) => '+',
+            });
+        }
+        result.push('\n');
+    }
+    result
+}
+
+// A binary PPM: sensors in red, beacons in blue, and coverage shaded from white for none to dark
+// grey for all.
+pub fn render_ppm(sensors: &[Sensor], viewport: &Viewport) -> Vec<u8> {
+    let pixels = pixels(sensors, viewport);
+    let mut result = format!("P6\n{} {}\n255\n", viewport.width(), viewport.height()).into_bytes();
+    for pixel in pixels.into_iter().flatten() {
+        let colour = match pixel {
+            Pixel::Sensor => [220, 30, 30],
+            Pixel::Beacon => [30, 60, 220],
+            Pixel::Coverage(fraction) => {
+                let shade = (255.0 - fraction * 200.0) as u8;
+                [shade, shade, shade]
+            }
+        };
+        result.extend(colour);
+    }
+    result
+}
@@ -0,0 +1,552 @@
+mod memoised;
+mod parser;
+mod plan;
+mod shortest_paths;
+mod thread;
+mod valve_set;
+mod walkthrough;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display},
+    rc::Rc,
+    time::Instant,
+};
+
+use ::utils::{numbered_lines, Graph, ParseError, Solution};
+use itertools::Itertools;
+use shortest_paths::ShortestPaths;
+
+pub use crate::{
+    memoised::best_plan,
+    plan::{Agent, Opening, Plan, Schedule},
+    thread::{Bound, SearchStats},
+    walkthrough::write_walkthrough,
+};
+use crate::{
+    parser::parse_valve,
+    shortest_paths::{all_shortest_paths, tunnels},
+    thread::{pruning_search, Best, Thread},
+    valve_set::ValveSet,
+};
+
+// A valve's position in `Volcano::valves`, which is the order the valves were given in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ValveId(u32);
+
+impl ValveId {
+    fn idx(self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Debug)]
+pub struct Valve {
+    id: ValveId,
+    name: String,
+    flow_rate: u32,
+    // In the order they were given.
+    neighbours: Vec<ValveId>,
+}
+
+// Why some valves don't make a volcano. Each valve is referred to by its position in the order
+// given, so a parser can point at the line it came from.
+#[derive(Debug, PartialEq, Eq)]
+pub enum InvalidValves<'a> {
+    // A valve with the same name as one before it.
+    Duplicate { idx: usize, name: &'a str },
+    UnknownNeighbour { idx: usize, neighbour: &'a str },
+    TooManyUseful { count: usize },
+}
+
+impl InvalidValves<'_> {
+    pub fn expected(&self) -> String {
+        match self {
+            Self::Duplicate { .. } => "a valve that isn't given twice".to_owned(),
+            Self::UnknownNeighbour { .. } => "the name of a valve in the volcano".to_owned(),
+            Self::TooManyUseful { .. } => format!(
+                "at most {} valves with a non-zero flow rate",
+                ValveSet::MAX_VALVES
+            ),
+        }
+    }
+
+    pub fn found(&self) -> String {
+        match self {
+            Self::Duplicate { name, .. } => name.to_string(),
+            Self::UnknownNeighbour { neighbour, .. } => neighbour.to_string(),
+            Self::TooManyUseful { count } => count.to_string(),
+        }
+    }
+}
+
+impl Display for InvalidValves<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {}, found {}", self.expected(), self.found())
+    }
+}
+
+pub struct Volcano {
+    valves: Vec<Valve>,
+    ids: HashMap<String, ValveId>,
+    tunnels: Graph<ValveId>,
+    shortest_paths: ShortestPaths,
+    // The valves with a non-zero flow rate, sorted by name. They're the only ones worth going to,
+    // and their positions here are their bits in a ValveSet.
+    useful_valves: Vec<ValveId>,
+    useful_indices: HashMap<ValveId, usize>,
+    flow_rates: Vec<u32>,
+    // Between useful valves, by index. u32::MAX if there's no way from one to the other.
+    distances: Vec<Vec<u32>>,
+    // Between the two closest useful valves, if there are two that are connected.
+    min_distance: Option<u32>,
+}
+
+impl Volcano {
+    // Each valve is given as its name, flow rate and the names of the valves its tunnels lead to,
+    // either from the input or e.g. for volcanoes made up in tests.
+    pub fn from_valves<'a>(
+        valves: impl IntoIterator<Item = (&'a str, u32, Vec<&'a str>)>,
+    ) -> Result<Self, InvalidValves<'a>> {
+        let valves: Vec<_> = valves.into_iter().collect();
+        let mut names = HashSet::new();
+        for (idx, (name, _, _)) in valves.iter().enumerate() {
+            if !names.insert(*name) {
+                return Err(InvalidValves::Duplicate { idx, name });
+            }
+        }
+        for (idx, (_, _, neighbours)) in valves.iter().enumerate() {
+            if let Some(&neighbour) = neighbours.iter().find(|name| !names.contains(*name)) {
+                return Err(InvalidValves::UnknownNeighbour { idx, neighbour });
+            }
+        }
+        let count = valves
+            .iter()
+            .filter(|(_, flow_rate, _)| *flow_rate > 0)
+            .count();
+        if count > ValveSet::MAX_VALVES {
+            return Err(InvalidValves::TooManyUseful { count });
+        }
+        Ok(Self::new(&valves))
+    }
+
+    // The names have to be unique, and the neighbours all have to be among them.
+    fn new(valves: &[(&str, u32, Vec<&str>)]) -> Self {
+        let ids: HashMap<String, ValveId> = (0..)
+            .zip(valves)
+            .map(|(id, (name, _, _))| (name.to_string(), ValveId(id)))
+            .collect();
+        let valves: Vec<_> = valves
+            .iter()
+            .map(|(name, flow_rate, neighbours)| Valve {
+                id: ids[*name],
+                name: name.to_string(),
+                flow_rate: *flow_rate,
+                neighbours: neighbours.iter().map(|neighbour| ids[*neighbour]).collect(),
+            })
+            .collect();
+
+        let tunnels = tunnels(&valves);
+        let shortest_paths =
+            all_shortest_paths(&valves, &tunnels).filter_out_faulty_valves(&valves);
+        let useful_valves: Vec<_> = valves
+            .iter()
+            .filter(|valve| valve.flow_rate > 0)
+            .sorted_by(|a, b| a.name.cmp(&b.name))
+            .map(|valve| valve.id)
+            .collect();
+        let mut volcano = Self {
+            useful_indices: useful_valves
+                .iter()
+                .enumerate()
+                .map(|(idx, id)| (*id, idx))
+                .collect(),
+            flow_rates: useful_valves
+                .iter()
+                .map(|id| valves[id.idx()].flow_rate)
+                .collect(),
+            useful_valves,
+            valves,
+            ids,
+            tunnels,
+            shortest_paths,
+            distances: vec![],
+            min_distance: None,
+        };
+        volcano.distances = volcano
+            .useful_valves
+            .iter()
+            .map(|&id| volcano.distances_from(id))
+            .collect();
+        volcano.min_distance = volcano
+            .distances
+            .iter()
+            .enumerate()
+            .flat_map(|(from, distances)| {
+                let others = distances
+                    .iter()
+                    .enumerate()
+                    .filter(move |(to, _)| *to != from);
+                others.map(|(_, &distance)| distance)
+            })
+            .filter(|&distance| distance != u32::MAX)
+            .min();
+        volcano
+    }
+
+    fn valve(&self, id: ValveId) -> &Valve {
+        &self.valves[id.idx()]
+    }
+
+    fn id(&self, name: &str) -> Option<ValveId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn has_valve(&self, name: &str) -> bool {
+        self.ids.contains_key(name)
+    }
+
+    fn id_or_error(&self, name: &str) -> Result<ValveId, String> {
+        self.id(name)
+            .ok_or_else(|| format!("there's no valve called {name}"))
+    }
+
+    // Where each agent starts, which has to be one of the volcano's valves.
+    fn starts(&self, agents: &[Agent]) -> Result<Vec<ValveId>, String> {
+        agents
+            .iter()
+            .map(|agent| self.id_or_error(&agent.start))
+            .collect()
+    }
+
+    // The valves passed through on one of the shortest ways from `from` to `to`, including `to`.
+    fn route(&self, from: ValveId, to: ValveId) -> Vec<ValveId> {
+        let route = self.tunnels.bfs_paths([from]).path(&to);
+        route.map_or(vec![], |route| route[1..].to_vec())
+    }
+
+    // From `source` to each useful valve.
+    fn distances_from(&self, source: ValveId) -> Vec<u32> {
+        let mut distances = vec![u32::MAX; self.useful_valves.len()];
+        for (target, distance) in self.shortest_paths.all_shortest_paths_from(source) {
+            distances[self.useful_indices[target]] = *distance;
+        }
+        distances
+    }
+}
+
+// A branch and bound search over every way of sharing the valves between the agents, which is
+// slower but handy for checking the memoised search.
+pub fn best_plan_by_pruning(
+    volcano: &Volcano,
+    agents: &[Agent],
+    bound: Bound,
+) -> Result<(Plan, SearchStats), String> {
+    let starts = volcano.starts(agents)?;
+    let start = Instant::now();
+    let mut stats = SearchStats::default();
+    let mut pruner = |thread_set: &[Rc<Thread>], best: &mut Best| {
+        let current_score = thread_set
+            .iter()
+            .map(|thread| thread.score(volcano))
+            .sum::<u32>();
+
+        let upper_bound = current_score + bound.max_remaining_value(thread_set, volcano);
+
+        if upper_bound <= best.released {
+            stats.pruned += 1;
+            false
+        } else {
+            stats.expanded += 1;
+            if current_score > best.released {
+                *best = Best {
+                    released: current_score,
+                    thread_set: thread_set.to_vec(),
+                };
+            }
+            true
+        }
+    };
+
+    let start_threads: Vec<_> = agents
+        .iter()
+        .zip(starts)
+        .map(|(agent, valve)| {
+            Rc::new(Thread::Start {
+                valve,
+                minutes: agent.minutes,
+            })
+        })
+        .collect();
+    let mut best = Best {
+        released: 0,
+        thread_set: start_threads.clone(),
+    };
+    pruning_search(&start_threads, volcano, &mut pruner, &mut best);
+    stats.elapsed = start.elapsed();
+
+    let plan = Plan {
+        schedules: agents
+            .iter()
+            .zip(&best.thread_set)
+            .map(|(agent, thread)| Schedule {
+                agent: agent.clone(),
+                openings: thread.openings(volcano),
+            })
+            .collect(),
+    };
+    Ok((plan, stats))
+}
+
+// Where the puzzle starts you off.
+pub const PUZZLE_START: &str = "AA";
+
+// You on your own for part 1, then you and the elephant for part 2 after teaching it.
+pub fn puzzle_agents(part: u8, start: &str) -> Vec<Agent> {
+    match part {
+        1 => vec![Agent::new(start, 30)],
+        _ => vec![Agent::new(start, 26), Agent::new(start, 26)],
+    }
+}
+
+// Either part's answer. The puzzle's agents start at AA, so there isn't one for volcanoes without
+// it.
+pub struct PuzzleAnswer(pub Result<Plan, String>);
+
+impl PuzzleAnswer {
+    fn for_part(volcano: &Volcano, part: u8) -> Self {
+        Self(best_plan(volcano, &puzzle_agents(part, PUZZLE_START)))
+    }
+}
+
+impl Display for PuzzleAnswer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Ok(plan) => write!(f, "{plan}"),
+            Err(message) => write!(f, "none, since {message}"),
+        }
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    const INPUT_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Volcano;
+    type Part1 = PuzzleAnswer;
+    type Part2 = PuzzleAnswer;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let valves: Vec<_> = numbered_lines(input)
+            .map(parse_valve)
+            .collect::<Result<_, _>>()?;
+        let volcano = Volcano::from_valves(
+            valves
+                .iter()
+                .map(|valve| (valve.name, valve.flow_rate, valve.neighbours.clone())),
+        );
+        // Point at the line with the problem where there is one.
+        volcano.map_err(|err| match err {
+            InvalidValves::Duplicate { idx, name: found }
+            | InvalidValves::UnknownNeighbour {
+                idx,
+                neighbour: found,
+            } => valves[idx].line.error(found, err.expected()),
+            InvalidValves::TooManyUseful { .. } => {
+                ParseError::invalid_input(err.expected(), err.found())
+            }
+        })
+    }
+
+    fn part1(volcano: &Self::Input) -> PuzzleAnswer {
+        PuzzleAnswer::for_part(volcano, 1)
+    }
+
+    fn part2(volcano: &Self::Input) -> PuzzleAnswer {
+        PuzzleAnswer::for_part(volcano, 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::utils::{check_answers, read_input, InputSource, Solution};
+    use itertools::Itertools;
+
+    use super::{
+        best_plan, best_plan_by_pruning, puzzle_agents, write_walkthrough, Agent, Bound, Day16,
+        Plan, Volcano, PUZZLE_START,
+    };
+
+    fn example_volcano() -> Volcano {
+        let input = read_input(
+            Day16::INPUT_DIR,
+            &InputSource::Variant("example".to_owned()),
+        );
+        Day16::parse(&input.unwrap()).unwrap()
+    }
+
+    // Nobody opens a valve twice, and everyone sticks to their own time.
+    fn check_plan(plan: &Plan, agents: &[Agent]) {
+        let opened: Vec<_> = plan
+            .schedules
+            .iter()
+            .flat_map(|schedule| &schedule.openings)
+            .map(|opening| &opening.valve)
+            .collect();
+        assert!(opened.iter().all_unique());
+        assert_eq!(plan.schedules.len(), agents.len());
+        for (schedule, agent) in plan.schedules.iter().zip(agents) {
+            assert_eq!(&schedule.agent, agent);
+            assert!(schedule
+                .openings
+                .iter()
+                .all(|opening| opening.minute < agent.minutes));
+        }
+    }
+
+    #[test]
+    fn example() {
+        check_answers::<Day16>("example");
+    }
+
+    #[test]
+    fn input() {
+        check_answers::<Day16>("input");
+    }
+
+    #[test]
+    fn searches_agree() {
+        let volcano = example_volcano();
+        let cases = [
+            vec![("AA", 30)],
+            vec![("AA", 26), ("AA", 26)],
+            vec![("AA", 10)],
+            vec![("AA", 10), ("AA", 10)],
+            vec![("AA", 5), ("AA", 5)],
+            vec![("AA", 12), ("AA", 12), ("AA", 12)],
+            vec![("AA", 20), ("JJ", 8)],
+            vec![("BB", 6), ("II", 15), ("EE", 9)],
+        ];
+        for agents in cases {
+            let agents: Vec<_> = agents
+                .into_iter()
+                .map(|(start, minutes)| Agent::new(start, minutes))
+                .collect();
+            let plan = best_plan(&volcano, &agents).unwrap();
+            check_plan(&plan, &agents);
+            for bound in Bound::ALL {
+                let (plan_by_pruning, _) = best_plan_by_pruning(&volcano, &agents, bound).unwrap();
+                assert_eq!(plan.released(), plan_by_pruning.released());
+                check_plan(&plan_by_pruning, &agents);
+            }
+        }
+    }
+
+    // A ring of valves with a few shortcuts across it, named at runtime.
+    #[test]
+    fn generated_volcano() {
+        let names: Vec<_> = (0..12).map(|idx| format!("V{idx}")).collect();
+        let volcano = Volcano::from_valves((0..12).map(|idx| {
+            let neighbours = [(idx + 1) % 12, (idx + 11) % 12, (idx * 5) % 12];
+            let neighbours = neighbours.iter().map(|&idx| names[idx].as_str()).collect();
+            (names[idx].as_str(), (idx as u32 * 7) % 11, neighbours)
+        }))
+        .unwrap();
+        drop(names);
+
+        for agents in [
+            vec![Agent::new("V0", 20)],
+            vec![Agent::new("V0", 12), Agent::new("V6", 12)],
+        ] {
+            let plan = best_plan(&volcano, &agents).unwrap();
+            for bound in Bound::ALL {
+                let (plan_by_pruning, _) = best_plan_by_pruning(&volcano, &agents, bound).unwrap();
+                assert_eq!(plan.released(), plan_by_pruning.released());
+            }
+            check_plan(&plan, &agents);
+        }
+        let err = Volcano::from_valves([("AA", 0, vec!["BB"])]).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "expected the name of a valve in the volcano, found BB"
+        );
+        assert_eq!(
+            best_plan(&volcano, &[Agent::new("AA", 30)]),
+            Err("there's no valve called AA".to_owned())
+        );
+        assert!(best_plan_by_pruning(&volcano, &[Agent::new("AA", 30)], Bound::ALL[0]).is_err());
+    }
+
+    #[test]
+    fn invalid_valves_point_at_their_line() {
+        let input = "\
+Valve AA has flow rate=0; tunnels lead to valves BB, CC
+Valve BB has flow rate=13; tunnel leads to valve AA
+Valve AA has flow rate=2; tunnel leads to valve BB
+";
+        let err = Day16::parse(input).err().unwrap();
+        assert_eq!((err.line, err.column, err.found.as_str()), (3, 7, "AA"));
+        assert_eq!(err.expected, "a valve that isn't given twice");
+
+        let input = input.replace(
+            "rate=2; tunnel leads to valve BB",
+            "rate=2; tunnel leads to valve DD",
+        );
+        let input = input.replace("Valve AA has flow rate=2", "Valve CC has flow rate=2");
+        let err = Day16::parse(&input).err().unwrap();
+        assert_eq!((err.line, err.column, err.found.as_str()), (3, 49, "DD"));
+        assert_eq!(err.expected, "the name of a valve in the volcano");
+    }
+
+    #[test]
+    fn walkthrough_matches_puzzle() {
+        let volcano = example_volcano();
+        let plan = best_plan(&volcano, &puzzle_agents(1, PUZZLE_START)).unwrap();
+        let mut walkthrough = vec![];
+        write_walkthrough(&volcano, &plan, &mut walkthrough).unwrap();
+        let walkthrough = String::from_utf8(walkthrough).unwrap();
+
+        // The puzzle doesn't keep a running total.
+        let (totals, puzzle_lines): (Vec<_>, Vec<_>) = walkthrough
+            .lines()
+            .partition(|line| line.ends_with("pressure released so far."));
+        assert_eq!(totals.last(), Some(&"1651 pressure released so far."));
+        let expected = "\
+== Minute 1 ==
+No valves are open.
+You move to valve DD.
+
+== Minute 2 ==
+No valves are open.
+You open valve DD.
+
+== Minute 3 ==
+Valve DD is open, releasing 20 pressure.
+You move to valve CC.
+
+== Minute 4 ==
+Valve DD is open, releasing 20 pressure.
+You move to valve BB.
+
+== Minute 5 ==
+Valve DD is open, releasing 20 pressure.
+You open valve BB.
+
+== Minute 6 ==
+Valves BB and DD are open, releasing 33 pressure.
+You move to valve AA.
+";
+        assert_eq!(
+            puzzle_lines[..expected.lines().count()].join("\n") + "\n",
+            expected
+        );
+        assert_eq!(
+            puzzle_lines[puzzle_lines.len() - 2..],
+            [
+                "== Minute 30 ==",
+                "Valves BB, CC, DD, EE, HH, and JJ are open, releasing 81 pressure."
+            ]
+        );
+    }
+}
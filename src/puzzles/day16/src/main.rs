@@ -1,126 +1,138 @@
-mod parser;
-mod shortest_paths;
-mod thread;
-mod utils;
 use std::{
-    collections::{HashMap, HashSet},
-    rc::Rc,
+    env,
+    io::{self, Write},
 };
 
-use itertools::Itertools;
-use shortest_paths::ShortestPaths;
-
-use crate::{
-    parser::parse_valve,
-    shortest_paths::floyd_warshall_shortest_paths,
-    thread::{all_opened_valves, max_remaining_value, pruning_search, Thread},
+use day16::{
+    best_plan, best_plan_by_pruning, puzzle_agents, write_walkthrough, Agent, Bound, Day16,
+    Volcano, PUZZLE_START,
 };
+use utils::{exit_with_error, take_flag, take_number, take_value, Answers};
 
-pub type ValveLookup = HashMap<&'static str, Valve>;
+enum Search {
+    Memoised,
+    Pruning(Bound),
+}
 
-#[derive(Debug)]
-pub struct Valve {
-    name: &'static str,
-    flow_rate: u32,
-    neighbours: HashSet<&'static str>,
+struct PlanOptions {
+    agents: Vec<Agent>,
+    walkthrough: bool,
+    search: Search,
 }
 
-impl Valve {
-    fn new(
-        name: &'static str,
-        flow_rate: &str,
-        neighbours: impl Iterator<Item = &'static str>,
-    ) -> Self {
-        Self {
-            name,
-            flow_rate: flow_rate.parse().unwrap(),
-            neighbours: neighbours.collect(),
+impl PlanOptions {
+    // Returns None unless --agents or --part was given. The puzzle's agents set off from `start`.
+    fn take(args: &mut Vec<String>, start: &str) -> Result<Option<Self>, String> {
+        let agents = match (
+            take_value(args, "--agents")?,
+            take_number::<u8>(args, "--part")?,
+        ) {
+            (None, None) => None,
+            (Some(agents), None) => Some(parse_agents(&agents)?),
+            (None, Some(part @ (1 | 2))) => Some(puzzle_agents(part, start)),
+            (None, Some(part)) => {
+                return Err(format!("expected part 1 or 2 after --part, found {part}"))
+            }
+            (Some(_), Some(_)) => return Err("expected only one of --agents and --part".to_owned()),
+        };
+        let walkthrough = take_flag(args, "--walkthrough");
+        let search = match (take_flag(args, "--pruning"), take_value(args, "--bound")?) {
+            (false, None) => Search::Memoised,
+            (_, None) => Search::Pruning(Bound::SortedFlowRates),
+            (_, Some(name)) => Search::Pruning(parse_bound(&name)?),
+        };
+        let Some(agents) = agents else {
+            if walkthrough || matches!(search, Search::Pruning(_)) {
+                return Err(
+                    "expected --agents or --part to say who's opening the valves".to_owned(),
+                );
+            }
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            agents,
+            walkthrough,
+            search,
+        }))
+    }
+
+    fn run(&self, volcano: &Volcano) -> Result<(), String> {
+        let agents = &self.agents;
+        let (plan, stats) = match self.search {
+            Search::Memoised => (best_plan(volcano, agents)?, None),
+            Search::Pruning(bound) => {
+                let (plan, stats) = best_plan_by_pruning(volcano, agents, bound)?;
+                (plan, Some(stats))
+            }
+        };
+        if self.walkthrough {
+            let mut out = io::stdout().lock();
+            write_walkthrough(volcano, &plan, &mut out)
+                .and_then(|_| out.flush())
+                .map_err(|err| format!("failed to write the walkthrough: {err}"))?;
+        } else {
+            print!("{}", plan.report());
         }
+        if let Some(stats) = stats {
+            println!();
+            print!("{stats}");
+        }
+        Ok(())
     }
 }
 
-fn earliest_times_to_open_reachable_valves(
-    thread_set: &[Rc<Thread>],
-    shortest_paths: &ShortestPaths,
-    total_runtime: u32,
-) -> HashMap<&'static str, u32> {
-    let already_opened_valves = all_opened_valves(thread_set);
+// Agents separated by commas, each a start valve and a number of minutes, e.g. "AA:26,AA:26".
+fn parse_agents(agents: &str) -> Result<Vec<Agent>, String> {
+    agents
+        .split(',')
+        .map(|agent| {
+            let (start, minutes) = agent
+                .trim()
+                .split_once(':')
+                .ok_or_else(|| format!("expected an agent like AA:26, found {agent}"))?;
+            let minutes = minutes
+                .parse()
+                .map_err(|_| format!("expected a number of minutes, found {minutes}"))?;
+            Ok(Agent::new(start, minutes))
+        })
+        .collect()
+}
 
-    thread_set
-        .iter()
-        .map(|thread| {
-            thread.earliest_times_to_open_reachable_closed_valves(
-                shortest_paths,
-                total_runtime,
-                &already_opened_valves,
+fn parse_bound(name: &str) -> Result<Bound, String> {
+    Bound::ALL
+        .into_iter()
+        .find(|bound| bound.name() == name)
+        .ok_or_else(|| {
+            let names: Vec<_> = Bound::ALL.iter().map(Bound::name).collect();
+            format!(
+                "expected one of {} after --bound, found {name}",
+                names.join(", ")
             )
         })
-        .reduce(|acc, thread_reachable_closed_valves| {
-            acc.keys()
-                .chain(thread_reachable_closed_valves.keys())
-                .unique()
-                .map(|valve_name| {
-                    let earliest_valve_could_be_opened = acc
-                        .get(valve_name)
-                        .into_iter()
-                        .chain(thread_reachable_closed_valves.get(valve_name))
-                        .min()
-                        .cloned()
-                        .unwrap();
+}
 
-                    (*valve_name, earliest_valve_could_be_opened)
-                })
-                .collect()
-        })
-        .unwrap_or_default()
+// Both parts' answers, with the agents setting off from `start`.
+fn answers(volcano: &Volcano, start: &str) -> Result<Answers, String> {
+    Ok(Answers {
+        part_1: best_plan(volcano, &puzzle_agents(1, start))?.to_string(),
+        part_2: best_plan(volcano, &puzzle_agents(2, start))?.to_string(),
+    })
 }
 
 fn main() {
-    let input = include_str!("../input.txt");
-    let valve_lookup: ValveLookup = input.lines().map(parse_valve).collect();
-    let shortest_paths =
-        floyd_warshall_shortest_paths(&valve_lookup).filter_out_faulty_valves(&valve_lookup);
-
-    let mut pruner = |thread_set: &[Rc<Thread>], total_runtime: u32, result: &mut u32| {
-        let current_score = thread_set
-            .iter()
-            .map(|thread| thread.score(&valve_lookup, total_runtime))
-            .sum::<u32>();
-
-        let upper_bound = current_score
-            + max_remaining_value(
-                earliest_times_to_open_reachable_valves(thread_set, &shortest_paths, total_runtime),
-                total_runtime,
-                &valve_lookup,
-            );
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let start = take_value(&mut args, "--start").unwrap_or_else(|message| exit_with_error(message));
+    let options = PlanOptions::take(&mut args, start.as_deref().unwrap_or(PUZZLE_START))
+        .unwrap_or_else(|message| exit_with_error(message));
+    if options.is_none() && start.is_none() {
+        return utils::run_with_args::<Day16>(args.into_iter());
+    }
+    let volcano = utils::load_input::<Day16>(args.into_iter());
 
-        if upper_bound <= *result {
-            false
-        } else {
-            *result = u32::max(current_score, *result);
-            true
-        }
+    let result = match (options, start) {
+        (Some(options), _) => options.run(&volcano),
+        (None, start) => answers(&volcano, start.as_deref().unwrap_or(PUZZLE_START))
+            .map(|answers| print!("{answers}")),
     };
-
-    let mut part_1_answer = 0;
-    pruning_search(
-        &[Rc::new(Thread::Start)],
-        &shortest_paths,
-        30,
-        &mut pruner,
-        &mut part_1_answer,
-    );
-
-    println!("part 1: {part_1_answer}");
-
-    let mut part_2_answer = 0;
-    pruning_search(
-        &[Rc::new(Thread::Start), Rc::new(Thread::Start)],
-        &shortest_paths,
-        26,
-        &mut pruner,
-        &mut part_2_answer,
-    );
-
-    println!("part 2: {part_2_answer}");
+    result.unwrap_or_else(|message| exit_with_error(message));
 }
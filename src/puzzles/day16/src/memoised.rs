@@ -0,0 +1,208 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use itertools::Itertools;
+
+use crate::{
+    plan::{Agent, Opening, Plan, Schedule},
+    valve_set::ValveSet,
+    ValveId, Volcano,
+};
+
+// The valve an agent has just opened and the minutes it has left.
+type Position = (usize, u32);
+
+// The valves that can still be opened in time, with the minutes left after opening each one.
+fn next_valves<'a>(
+    volcano: &'a Volcano,
+    distances: &'a [u32],
+    minutes_left: u32,
+    opened: ValveSet,
+) -> impl Iterator<Item = Position> + 'a {
+    (0..volcano.useful_valves.len())
+        .filter(move |&idx| !opened.contains(idx))
+        .filter_map(move |idx| {
+            // Opening a valve in the last minute doesn't release anything.
+            let minutes_left = minutes_left
+                .checked_sub(distances[idx].saturating_add(1))
+                .filter(|&minutes_left| minutes_left > 0)?;
+            Some((idx, minutes_left))
+        })
+}
+
+// Everything one agent could do on its own. Goes forwards through the minutes, keeping only the
+// best way of reaching each (position, opened valves), since what happens after that doesn't
+// depend on how it got there, but remembering where that best way came from.
+struct AgentSearch {
+    minutes: u32,
+    // The pressure released so far and the previous position, if there was one.
+    states: HashMap<(Position, ValveSet), (u32, Option<Position>)>,
+    // The most released by opening exactly each set of valves it can get to in time, and where it
+    // ends up.
+    best: HashMap<ValveSet, (u32, Option<Position>)>,
+}
+
+impl AgentSearch {
+    fn new(volcano: &Volcano, agent: &Agent, start: ValveId) -> Self {
+        let mut search = Self {
+            minutes: agent.minutes,
+            states: HashMap::new(),
+            best: HashMap::from([(ValveSet::EMPTY, (0, None))]),
+        };
+        let mut positions_by_minutes_left = vec![vec![]; agent.minutes as usize + 1];
+        let start_distances = volcano.distances_from(start);
+        for position in next_valves(volcano, &start_distances, agent.minutes, ValveSet::EMPTY) {
+            let (idx, minutes_left) = position;
+            let released = volcano.flow_rates[idx] * minutes_left;
+            let opened = ValveSet::EMPTY.with(idx);
+            search.states.insert((position, opened), (released, None));
+            positions_by_minutes_left[minutes_left as usize].push((idx, opened));
+        }
+
+        for minutes_left in (1..=agent.minutes).rev() {
+            let positions = std::mem::take(&mut positions_by_minutes_left[minutes_left as usize]);
+            for (idx, opened) in positions {
+                let position = (idx, minutes_left);
+                let released = search.states[&(position, opened)].0;
+                let best = search.best.entry(opened).or_insert((0, None));
+                if released > best.0 {
+                    *best = (released, Some(position));
+                }
+                let distances = &volcano.distances[idx];
+                for next in next_valves(volcano, distances, minutes_left, opened) {
+                    let (idx, next_minutes_left) = next;
+                    let next_released = released + volcano.flow_rates[idx] * next_minutes_left;
+                    let next_opened = opened.with(idx);
+                    match search.states.get_mut(&(next, next_opened)) {
+                        Some(state) if state.0 >= next_released => {}
+                        Some(state) => *state = (next_released, Some(position)),
+                        None => {
+                            search
+                                .states
+                                .insert((next, next_opened), (next_released, Some(position)));
+                            positions_by_minutes_left[next_minutes_left as usize]
+                                .push((idx, next_opened));
+                        }
+                    }
+                }
+            }
+        }
+        search
+    }
+
+    fn released_by_opened_valves(&self) -> impl Iterator<Item = (ValveSet, u32)> + '_ {
+        self.best
+            .iter()
+            .map(|(valves, (released, _))| (*valves, *released))
+    }
+
+    // The best way of opening exactly `valves`, following the states back from where it ends.
+    fn schedule(&self, volcano: &Volcano, agent: &Agent, mut valves: ValveSet) -> Schedule {
+        let mut openings = vec![];
+        let mut position = self.best[&valves].1;
+        while let Some((idx, minutes_left)) = position {
+            openings.push(Opening {
+                valve: volcano.valve(volcano.useful_valves[idx]).name.clone(),
+                minute: self.minutes - minutes_left,
+                released: volcano.flow_rates[idx] * minutes_left,
+            });
+            position = self.states[&((idx, minutes_left), valves)].1;
+            valves = valves.without(idx);
+        }
+        openings.reverse();
+        Schedule {
+            agent: agent.clone(),
+            openings,
+        }
+    }
+}
+
+// For each set of valves a group of agents could open between them, the most they can release
+// and the valves the last agent to join the group opens.
+type Group = HashMap<ValveSet, (u32, ValveSet)>;
+
+// Adds one more agent to a group, without opening the same valve twice.
+fn combine_disjoint(group: &Group, agent: &AgentSearch) -> Group {
+    let mut result = HashMap::new();
+    for (valves_a, (released_a, _)) in group {
+        for (valves_b, released_b) in agent.released_by_opened_valves() {
+            if valves_a.is_disjoint(valves_b) {
+                let best = result
+                    .entry(valves_a.union(valves_b))
+                    .or_insert((0, ValveSet::EMPTY));
+                if released_a + released_b > best.0 {
+                    *best = (released_a + released_b, valves_b);
+                }
+            }
+        }
+    }
+    result
+}
+
+fn sorted_by_released(best: impl Iterator<Item = (ValveSet, u32)>) -> Vec<(ValveSet, u32)> {
+    best.sorted_unstable_by_key(|(_, released)| Reverse(*released))
+        .collect()
+}
+
+// The same, but only the overall best when the last agent joins. Going through both from the
+// most released down means most pairs never need looking at.
+fn best_disjoint_pair(group: &Group, agent: &AgentSearch) -> (ValveSet, ValveSet) {
+    let sorted_agent = sorted_by_released(agent.released_by_opened_valves());
+    let sorted_group = sorted_by_released(
+        group
+            .iter()
+            .map(|(valves, (released, _))| (*valves, *released)),
+    );
+    let mut result = (0, ValveSet::EMPTY, ValveSet::EMPTY);
+    for (valves_a, released_a) in sorted_group {
+        for &(valves_b, released_b) in &sorted_agent {
+            if released_a + released_b <= result.0 {
+                break;
+            }
+            if valves_a.is_disjoint(valves_b) {
+                result = (released_a + released_b, valves_a, valves_b);
+                break;
+            }
+        }
+    }
+    (result.1, result.2)
+}
+
+// The best each agent could do with every set of valves it could open is worked out once (once
+// for all agents with the same start and time), then the agents join a group one at a time,
+// sharing the valves out between them. The winning sets are followed back to who opens what.
+pub fn best_plan(volcano: &Volcano, agents: &[Agent]) -> Result<Plan, String> {
+    let starts = volcano.starts(agents)?;
+    let mut searches: HashMap<&Agent, AgentSearch> = HashMap::new();
+    for (agent, start) in agents.iter().zip(starts) {
+        if !searches.contains_key(agent) {
+            searches.insert(agent, AgentSearch::new(volcano, agent, start));
+        }
+    }
+    let Some((last_agent, other_agents)) = agents.split_last() else {
+        return Ok(Plan::default());
+    };
+
+    let mut groups = vec![];
+    let mut group: Group = HashMap::from([(ValveSet::EMPTY, (0, ValveSet::EMPTY))]);
+    for agent in other_agents {
+        group = combine_disjoint(&group, &searches[agent]);
+        groups.push(group.clone());
+    }
+    let (mut group_valves, last_valves) = best_disjoint_pair(&group, &searches[last_agent]);
+
+    let mut valves_by_agent = vec![last_valves];
+    for group in groups.iter().rev() {
+        let agent_valves = group[&group_valves].1;
+        valves_by_agent.push(agent_valves);
+        group_valves = group_valves.difference(agent_valves);
+    }
+    valves_by_agent.reverse();
+
+    Ok(Plan {
+        schedules: agents
+            .iter()
+            .zip(valves_by_agent)
+            .map(|(agent, valves)| searches[agent].schedule(volcano, agent, valves))
+            .collect(),
+    })
+}
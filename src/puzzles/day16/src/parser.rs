@@ -1,12 +1,32 @@
-use crate::Valve;
+use utils::{Line, ParseError};
 
-pub fn parse_valve(line: &'static str) -> (&str, Valve) {
-    let parts: Vec<_> = line.split_ascii_whitespace().collect();
-    let name = parts[1];
-    let flow_rate = parts[4]
-        .strip_prefix("rate=")
-        .and_then(|rhs| rhs.strip_suffix(';'))
-        .unwrap();
-    let neighbours = parts[9..].iter().map(|neighb| neighb.trim_end_matches(','));
-    (name, Valve::new(name, flow_rate, neighbours))
+// A valve as it's written in the input: its name, flow rate and where its tunnels lead.
+pub struct ValveLine<'a> {
+    pub line: Line<'a>,
+    pub name: &'a str,
+    pub flow_rate: u32,
+    pub neighbours: Vec<&'a str>,
+}
+
+pub fn parse_valve(line: Line<'_>) -> Result<ValveLine<'_>, ParseError> {
+    let parts = line.tokens();
+    let name = parts.get(1, "a valve name")?;
+    let flow_rate = parts.get(4, "the flow rate")?;
+    let flow_rate = line.strip_prefix(flow_rate, "rate=")?;
+    let flow_rate = line.strip_suffix(flow_rate, ";")?;
+    let flow_rate = line.parse(flow_rate, "a flow rate")?;
+    let neighbours = parts.rest(9);
+    if neighbours.is_empty() {
+        return Err(line.error_at_end("at least one neighbouring valve"));
+    }
+    let neighbours = neighbours
+        .iter()
+        .map(|neighb| neighb.trim_end_matches(','))
+        .collect();
+    Ok(ValveLine {
+        line,
+        name,
+        flow_rate,
+        neighbours,
+    })
 }
@@ -0,0 +1,80 @@
+use std::fmt::{self, Display, Write};
+
+// Someone opening valves: where they set off from and how many minutes they have.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Agent {
+    pub start: String,
+    pub minutes: u32,
+}
+
+impl Agent {
+    pub fn new(start: &str, minutes: u32) -> Self {
+        Self {
+            start: start.to_owned(),
+            minutes,
+        }
+    }
+}
+
+// A valve opened in minute `minute` of the agent's time, and what it releases by the end of it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Opening {
+    pub valve: String,
+    pub minute: u32,
+    pub released: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Schedule {
+    pub agent: Agent,
+    // In the order they're opened.
+    pub openings: Vec<Opening>,
+}
+
+impl Schedule {
+    pub fn released(&self) -> u32 {
+        self.openings.iter().map(|opening| opening.released).sum()
+    }
+}
+
+// Who opens which valves when, one schedule per agent in the order the agents were given.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Plan {
+    pub schedules: Vec<Schedule>,
+}
+
+impl Plan {
+    pub fn released(&self) -> u32 {
+        self.schedules.iter().map(Schedule::released).sum()
+    }
+
+    pub fn report(&self) -> String {
+        let mut report = String::new();
+        for (number, schedule) in (1..).zip(&self.schedules) {
+            let Agent { start, minutes } = &schedule.agent;
+            writeln!(report, "Agent {number} (from {start}, {minutes} minutes):").unwrap();
+            for Opening {
+                valve,
+                minute,
+                released,
+            } in &schedule.openings
+            {
+                writeln!(
+                    report,
+                    "  minute {minute}: opens {valve}, releasing {released}"
+                )
+                .unwrap();
+            }
+            writeln!(report, "  released {}", schedule.released()).unwrap();
+        }
+        writeln!(report, "total released: {}", self.released()).unwrap();
+        report
+    }
+}
+
+// Just the total, which is the answer to both parts.
+impl Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.released())
+    }
+}
@@ -1,180 +1,288 @@
 use std::{
+    cmp::Reverse,
     collections::{HashMap, HashSet},
+    fmt::{self, Display},
     rc::Rc,
+    time::Duration,
 };
 
 use itertools::Itertools;
 
-use crate::{shortest_paths::ShortestPaths, utils::combinations_taking_one_from_each, ValveLookup};
+use crate::{plan::Opening, valve_set::ValveSet, ValveId, Volcano};
 
+// The valves one agent has opened so far, latest first.
 #[derive(Debug)]
 pub enum Thread {
-    Start,
+    Start {
+        valve: ValveId,
+        minutes: u32,
+    },
     Extension {
-        opened_valve: &'static str,
+        opened_valve: ValveId,
         minute_opened: u32,
+        // Every valve opened along the thread so far, including this one.
+        opened_valves: ValveSet,
         prev: Rc<Thread>,
     },
+    // The agent won't open anything else.
     Done {
         prev: Rc<Thread>,
     },
 }
 
-pub fn pruning_search<'a, F: FnMut(&[Rc<Thread>], u32, &mut u32) -> bool>(
+// The best thread set found so far and how much it releases.
+pub struct Best {
+    pub released: u32,
+    pub thread_set: Vec<Rc<Thread>>,
+}
+
+// How much work a pruning search did: the thread sets it went on to extend, the ones its bound
+// ruled out, and how long it took.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SearchStats {
+    pub expanded: u64,
+    pub pruned: u64,
+    pub elapsed: Duration,
+}
+
+impl Display for SearchStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "nodes expanded: {}", self.expanded)?;
+        writeln!(f, "nodes pruned: {}", self.pruned)?;
+        writeln!(f, "took {:.2?}", self.elapsed)
+    }
+}
+
+pub fn pruning_search<F: FnMut(&[Rc<Thread>], &mut Best) -> bool>(
     thread_set: &[Rc<Thread>],
-    shortest_paths: &'a ShortestPaths,
-    total_runtime: u32,
+    volcano: &Volcano,
     is_potential_solution: &mut F,
-    result: &mut u32,
+    result: &mut Best,
 ) {
-    if is_potential_solution(thread_set, total_runtime, result) {
-        for extended_thread_set in extensions(thread_set, shortest_paths, total_runtime) {
-            pruning_search(
-                &extended_thread_set,
-                shortest_paths,
-                total_runtime,
-                is_potential_solution,
-                result,
-            );
+    if is_potential_solution(thread_set, result) {
+        for extended_thread_set in extensions(thread_set, volcano) {
+            pruning_search(&extended_thread_set, volcano, is_potential_solution, result);
         }
     }
 }
 
-pub fn all_opened_valves(thread_set: &[Rc<Thread>]) -> HashSet<&'static str> {
-    thread_set
-        .iter()
-        .map(|thread| thread.all_opened_valves())
-        .reduce(|acc, x| acc.union(&x).cloned().collect())
-        .unwrap_or_default()
+pub fn all_opened_valves(thread_set: &[Rc<Thread>]) -> ValveSet {
+    thread_set.iter().fold(ValveSet::EMPTY, |acc, thread| {
+        acc.union(thread.opened_valves())
+    })
 }
 
-fn extensions(
-    thread_set: &[Rc<Thread>],
-    shortest_paths: &ShortestPaths,
-    total_runtime: u32,
-) -> Vec<Vec<Rc<Thread>>> {
+// Only one thread is extended at a time, the one furthest behind that isn't done, either to a valve
+// nobody has opened or by stopping it. The agents only get in each other's way by opening the same
+// valve, so this still reaches every way of sharing out the valves.
+fn extensions(thread_set: &[Rc<Thread>], volcano: &Volcano) -> Vec<Vec<Rc<Thread>>> {
     let opened_valves = all_opened_valves(thread_set);
-
-    let possible_extensions: Vec<Vec<_>> = thread_set
+    let Some((thread_idx, thread)) = thread_set
         .iter()
-        .map(|thread| {
-            let reachable_valves: HashMap<&str, u32> = thread
-                .earliest_times_to_open_reachable_closed_valves(
-                    shortest_paths,
-                    total_runtime,
-                    &opened_valves,
-                )
-                .into_iter()
-                .filter(|(k, _)| !opened_valves.contains(k))
-                .collect();
-
-            let mut result: Vec<_> = reachable_valves
-                .into_iter()
-                .map(|(target, minute_opened)| {
-                    Rc::new(Thread::Extension {
-                        minute_opened,
-                        opened_valve: target,
-                        prev: thread.clone(),
-                    })
-                })
-                .collect();
-
-            // This is necessary to make it possible for some threads to continue even when others have no remaining possible extensions.
-            if result.is_empty() {
-                result.push(Rc::new(Thread::Done {
-                    prev: thread.clone(),
-                }))
-            }
+        .enumerate()
+        .filter(|(_, thread)| !thread.is_done())
+        .min_by_key(|(_, thread)| thread.minute_opened())
+    else {
+        return vec![];
+    };
 
-            result
+    let reachable_valves =
+        thread.earliest_times_to_open_reachable_closed_valves(volcano, opened_valves);
+    let mut extended_threads: Vec<_> = reachable_valves
+        .into_iter()
+        .map(|(target, minute_opened)| {
+            Rc::new(Thread::Extension {
+                minute_opened,
+                opened_valve: target,
+                opened_valves: thread.opened_valves().with(volcano.useful_indices[&target]),
+                prev: thread.clone(),
+            })
         })
         .collect();
+    extended_threads.push(Rc::new(Thread::Done {
+        prev: thread.clone(),
+    }));
 
-    combinations_taking_one_from_each(possible_extensions)
+    extended_threads
         .into_iter()
-        .filter(|extension_combination| {
-            extension_combination
-                .iter()
-                .map(|thr| thr.current_valve())
-                .all_unique()
+        .map(|extended_thread| {
+            let mut extended_thread_set = thread_set.to_vec();
+            extended_thread_set[thread_idx] = extended_thread;
+            extended_thread_set
         })
         .collect()
 }
 
-pub fn max_remaining_value(
-    reachable_closed_valves: HashMap<&'static str, u32>,
-    total_runtime: u32,
-    valve_lookup: &ValveLookup,
-) -> u32 {
-    reachable_closed_valves
+// How the pruning search works out the most the agents could still release.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bound {
+    EarliestOpenings,
+    SortedFlowRates,
+}
+
+impl Bound {
+    pub const ALL: [Self; 2] = [Self::EarliestOpenings, Self::SortedFlowRates];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::EarliestOpenings => "earliest",
+            Self::SortedFlowRates => "sorted",
+        }
+    }
+
+    pub fn max_remaining_value(&self, thread_set: &[Rc<Thread>], volcano: &Volcano) -> u32 {
+        match self {
+            Self::EarliestOpenings => max_remaining_value_by_earliest_openings(thread_set, volcano),
+            Self::SortedFlowRates => max_remaining_value_by_sorted_flow_rates(thread_set, volcano),
+        }
+    }
+}
+
+// Assumes every closed valve is opened by whichever agent could get the most out of it, as early as
+// that agent could open it.
+fn max_remaining_value_by_earliest_openings(thread_set: &[Rc<Thread>], volcano: &Volcano) -> u32 {
+    let opened_valves = all_opened_valves(thread_set);
+    let mut best_values: HashMap<ValveId, u32> = HashMap::new();
+    for thread in thread_set {
+        let reachable =
+            thread.earliest_times_to_open_reachable_closed_valves(volcano, opened_valves);
+        for (valve, minute_opened) in reachable {
+            let flow_rate = volcano.valve(valve).flow_rate;
+            let value = flow_rate * (thread.minutes() - minute_opened);
+            let best = best_values.entry(valve).or_insert(0);
+            *best = u32::max(*best, value);
+        }
+    }
+    best_values.values().sum()
+}
+
+// An agent can't open its next valve any sooner than its nearest closed one, or each one after
+// that any sooner than the two closest useful valves are apart. Pairing the biggest flow rates
+// with the most minutes that leaves any agent can't be beaten by where the valves really are.
+fn max_remaining_value_by_sorted_flow_rates(thread_set: &[Rc<Thread>], volcano: &Volcano) -> u32 {
+    let opened_valves = all_opened_valves(thread_set);
+    let mut closed_valves = HashSet::new();
+    let mut minutes_left = vec![];
+    for thread in thread_set {
+        let reachable =
+            thread.earliest_times_to_open_reachable_closed_valves(volcano, opened_valves);
+        let Some(&first_minute_opened) = reachable.values().min() else {
+            continue;
+        };
+        closed_valves.extend(reachable.into_keys());
+        let mut minute_opened = first_minute_opened;
+        while minute_opened < thread.minutes() {
+            minutes_left.push(thread.minutes() - minute_opened);
+            let Some(distance) = volcano.min_distance else {
+                break;
+            };
+            minute_opened += distance + 1;
+        }
+    }
+    let flow_rates = closed_valves
         .into_iter()
-        .map(|(valve_name, minute_opened)| {
-            let valve = valve_lookup.get(valve_name).unwrap();
-            valve.flow_rate * (total_runtime - minute_opened)
-        })
+        .map(|valve| volcano.valve(valve).flow_rate)
+        .sorted_unstable_by_key(|&flow_rate| Reverse(flow_rate));
+    minutes_left.sort_unstable_by_key(|&minutes_left| Reverse(minutes_left));
+    flow_rates
+        .zip(minutes_left)
+        .map(|(flow_rate, minutes_left)| flow_rate * minutes_left)
         .sum()
 }
 
-impl<'a> Thread {
+impl Thread {
     fn minute_opened(&self) -> u32 {
         match self {
-            Self::Start => 0,
+            Self::Start { .. } => 0,
             Self::Extension { minute_opened, .. } => *minute_opened,
             Self::Done { prev } => prev.minute_opened(),
         }
     }
 
-    fn all_opened_valves(&self) -> HashSet<&'static str> {
+    // The agent's time budget.
+    fn minutes(&self) -> u32 {
+        match self {
+            Self::Start { minutes, .. } => *minutes,
+            Self::Extension { prev, .. } | Self::Done { prev } => prev.minutes(),
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        matches!(self, Self::Done { .. })
+    }
+
+    fn opened_valves(&self) -> ValveSet {
+        match self {
+            Self::Start { .. } => ValveSet::EMPTY,
+            Self::Extension { opened_valves, .. } => *opened_valves,
+            Self::Done { prev } => prev.opened_valves(),
+        }
+    }
+
+    pub fn score(&self, volcano: &Volcano) -> u32 {
         match self {
-            Self::Start => HashSet::new(),
+            Self::Start { .. } => 0,
             Self::Extension {
-                opened_valve, prev, ..
-            } => HashSet::from([*opened_valve])
-                .union(&prev.all_opened_valves())
-                .cloned()
-                .collect(),
-            Self::Done { prev } => prev.all_opened_valves(),
+                opened_valve,
+                minute_opened,
+                prev,
+                ..
+            } => {
+                let valve = volcano.valve(*opened_valve);
+                valve.flow_rate * (self.minutes() - minute_opened) + prev.score(volcano)
+            }
+            Self::Done { prev } => prev.score(volcano),
         }
     }
 
-    pub fn score(&self, valve_lookup: &ValveLookup, total_runtime: u32) -> u32 {
+    // The valves opened along the thread, in the order they were opened.
+    pub fn openings(&self, volcano: &Volcano) -> Vec<Opening> {
         match self {
-            Self::Start => 0,
+            Self::Start { .. } => vec![],
             Self::Extension {
                 opened_valve,
                 minute_opened,
                 prev,
+                ..
             } => {
-                let valve = valve_lookup.get(opened_valve).unwrap();
-                valve.flow_rate * (total_runtime - minute_opened)
-                    + prev.score(valve_lookup, total_runtime)
+                let valve = volcano.valve(*opened_valve);
+                let mut openings = prev.openings(volcano);
+                openings.push(Opening {
+                    valve: valve.name.clone(),
+                    minute: *minute_opened,
+                    released: valve.flow_rate * (self.minutes() - minute_opened),
+                });
+                openings
             }
-            Self::Done { prev } => prev.score(valve_lookup, total_runtime),
+            Self::Done { prev } => prev.openings(volcano),
         }
     }
 
-    fn current_valve(&self) -> &'static str {
+    fn current_valve(&self) -> ValveId {
         match self {
-            Self::Start => "AA",
-            Self::Extension { opened_valve, .. } => opened_valve,
+            Self::Start { valve, .. } => *valve,
+            Self::Extension { opened_valve, .. } => *opened_valve,
             Self::Done { prev } => prev.current_valve(),
         }
     }
 
+    // Nothing for a thread that's done.
     pub fn earliest_times_to_open_reachable_closed_valves(
         self: &Rc<Self>,
-        shortest_paths: &'a ShortestPaths,
-        total_runtime: u32,
-        opened_valves: &HashSet<&'static str>,
-    ) -> HashMap<&'static str, u32> {
-        shortest_paths
+        volcano: &Volcano,
+        opened_valves: ValveSet,
+    ) -> HashMap<ValveId, u32> {
+        if self.is_done() {
+            return HashMap::new();
+        }
+        volcano
+            .shortest_paths
             .all_shortest_paths_from(self.current_valve())
-            .into_iter()
-            .flatten()
-            .filter_map(move |(target, path_length)| {
+            .iter()
+            .filter_map(|(target, path_length)| {
                 let earliest_possible_minute_opened = self.minute_opened() + path_length + 1;
-                let is_valid = earliest_possible_minute_opened < total_runtime
-                    && !opened_valves.contains(target);
+                let is_valid = earliest_possible_minute_opened < self.minutes()
+                    && !opened_valves.contains(volcano.useful_indices[target]);
                 is_valid.then_some((*target, earliest_possible_minute_opened))
             })
             .collect()
@@ -0,0 +1,33 @@
+// A set of the valves worth opening, with bit i set for the i-th of `Volcano::useful_valves`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ValveSet(u64);
+
+impl ValveSet {
+    pub const MAX_VALVES: usize = u64::BITS as usize;
+
+    pub const EMPTY: Self = Self(0);
+
+    pub fn contains(&self, idx: usize) -> bool {
+        self.0 & (1 << idx) != 0
+    }
+
+    pub fn with(&self, idx: usize) -> Self {
+        Self(self.0 | 1 << idx)
+    }
+
+    pub fn without(&self, idx: usize) -> Self {
+        Self(self.0 & !(1 << idx))
+    }
+
+    pub fn union(&self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn difference(&self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    pub fn is_disjoint(&self, other: Self) -> bool {
+        self.0 & other.0 == 0
+    }
+}
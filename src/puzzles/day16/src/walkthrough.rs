@@ -0,0 +1,115 @@
+use std::io::{self, Write};
+
+use itertools::Itertools;
+
+use crate::{plan::Plan, ValveId, Volcano};
+
+enum Action<'a> {
+    Move(&'a str),
+    Open(&'a str),
+}
+
+// A plan made for a different volcano can name valves this one hasn't got.
+fn id(volcano: &Volcano, name: &str) -> io::Result<ValveId> {
+    volcano
+        .id_or_error(name)
+        .map_err(|message| io::Error::new(io::ErrorKind::InvalidInput, message))
+}
+
+// What each agent does in each minute, from minute 1, following a shortest route to each valve
+// it opens. Nothing once it's opened all its valves.
+fn actions<'a>(volcano: &'a Volcano, plan: &'a Plan) -> io::Result<Vec<Vec<Action<'a>>>> {
+    plan.schedules
+        .iter()
+        .map(|schedule| {
+            let mut actions = vec![];
+            let mut current = id(volcano, &schedule.agent.start)?;
+            for opening in &schedule.openings {
+                let valve = id(volcano, &opening.valve)?;
+                for step in volcano.route(current, valve) {
+                    actions.push(Action::Move(&volcano.valve(step).name));
+                }
+                actions.push(Action::Open(&opening.valve));
+                current = valve;
+            }
+            Ok(actions)
+        })
+        .collect()
+}
+
+// The puzzle calls the first agent "you" and the second the elephant.
+fn agent_name(idx: usize, agent_count: usize) -> String {
+    match idx {
+        0 => "You".to_owned(),
+        1 if agent_count == 2 => "The elephant".to_owned(),
+        _ => format!("Elephant {idx}"),
+    }
+}
+
+// `open_valves` sorted by name.
+fn write_open_valves(open_valves: &[&str], released: u32, out: &mut impl Write) -> io::Result<()> {
+    match open_valves {
+        [] => writeln!(out, "No valves are open."),
+        [name] => writeln!(out, "Valve {name} is open, releasing {released} pressure."),
+        [first, second] => writeln!(
+            out,
+            "Valves {first} and {second} are open, releasing {released} pressure."
+        ),
+        [rest @ .., last] => writeln!(
+            out,
+            "Valves {}, and {last} are open, releasing {released} pressure.",
+            rest.join(", ")
+        ),
+    }
+}
+
+// Minute by minute, worded like the puzzle's walkthrough, with the pressure released so far at the
+// end of each minute. A valve only counts while the agent that opened it still has time left,
+// which only matters when the agents have different amounts of time.
+pub fn write_walkthrough(volcano: &Volcano, plan: &Plan, out: &mut impl Write) -> io::Result<()> {
+    let actions = actions(volcano, plan)?;
+    let minutes = plan
+        .schedules
+        .iter()
+        .map(|schedule| schedule.agent.minutes)
+        .max()
+        .unwrap_or(0);
+    let mut released_so_far = 0;
+    for minute in 1..=minutes {
+        if minute > 1 {
+            writeln!(out)?;
+        }
+        writeln!(out, "== Minute {minute} ==")?;
+        let open_valves: Vec<_> = plan
+            .schedules
+            .iter()
+            .filter(|schedule| minute <= schedule.agent.minutes)
+            .flat_map(|schedule| &schedule.openings)
+            .filter(|opening| opening.minute < minute)
+            .map(|opening| opening.valve.as_str())
+            .sorted()
+            .collect();
+        let released = open_valves
+            .iter()
+            .map(|&valve| Ok(volcano.valve(id(volcano, valve)?).flow_rate))
+            .sum::<io::Result<_>>()?;
+        write_open_valves(&open_valves, released, out)?;
+        released_so_far += released;
+
+        for (idx, agent_actions) in actions.iter().enumerate() {
+            let name = agent_name(idx, actions.len());
+            let (move_verb, open_verb) = if idx == 0 {
+                ("move", "open")
+            } else {
+                ("moves", "opens")
+            };
+            match agent_actions.get(minute as usize - 1) {
+                Some(Action::Move(valve)) => writeln!(out, "{name} {move_verb} to valve {valve}.")?,
+                Some(Action::Open(valve)) => writeln!(out, "{name} {open_verb} valve {valve}.")?,
+                _ => {}
+            }
+        }
+        writeln!(out, "{released_so_far} pressure released so far.")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,380 @@
+use std::fmt::Debug;
+
+use utils::{numbered_lines, Line, ParseError};
+
+use crate::WindDirection;
+
+// Every row of the chamber is a bitmask, with bit x set if column x is filled.
+pub type Row = u32;
+
+const DEFAULT_ROCK_SHAPES: &str = "\
+####
+
+.#.
+###
+.#.
+
+..#
+..#
+###
+
+#
+#
+#
+#
+
+##
+##
+";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RockShape {
+    // Bottom row first, with the shape's leftmost column in bit 0.
+    rows: Vec<Row>,
+    width: usize,
+}
+
+impl RockShape {
+    // Shapes drawn the way the puzzle draws them, # for rock and . for space, with a blank line
+    // between shapes.
+    pub fn parse_all(input: &str) -> Result<Vec<Self>, ParseError> {
+        let lines: Vec<_> = numbered_lines(input).collect();
+        let shapes = lines
+            .split(|line| line.text.trim().is_empty())
+            .filter(|shape_lines| !shape_lines.is_empty())
+            .map(Self::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        if shapes.is_empty() {
+            return Err(ParseError::end_of_input("at least one rock shape"));
+        }
+        Ok(shapes)
+    }
+
+    fn parse(lines: &[Line]) -> Result<Self, ParseError> {
+        let mut rows = vec![];
+        for line in lines.iter().rev() {
+            let mut row: Row = 0;
+            for (idx, ch) in line.text.char_indices() {
+                match ch {
+                    '#' if idx < Row::BITS as usize => row |= 1 << idx,
+                    '#' => {
+                        let expected = format!("a shape at most {} wide", Row::BITS);
+                        return Err(line.error(&line.text[idx..], expected));
+                    }
+                    '.' => {}
+                    _ => return Err(line.error(&line.text[idx..idx + ch.len_utf8()], "# or .")),
+                }
+            }
+            rows.push(row);
+        }
+        if rows.iter().all(|row| *row == 0) {
+            return Err(lines[0].error(lines[0].text, "a shape with at least one #"));
+        }
+
+        // Blank rows and columns around the shape would only change where it appears.
+        while rows.last() == Some(&0) {
+            rows.pop();
+        }
+        let blank_rows_below = rows.iter().take_while(|row| **row == 0).count();
+        rows.drain(..blank_rows_below);
+        let blank_columns_left = rows.iter().map(|row| row.trailing_zeros()).min().unwrap();
+        for row in &mut rows {
+            *row >>= blank_columns_left;
+        }
+        let width = rows
+            .iter()
+            .map(|row| (Row::BITS - row.leading_zeros()) as usize)
+            .max()
+            .unwrap();
+        Ok(Self { rows, width })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ChamberConfig {
+    pub width: usize,
+    // New rocks appear with this many columns between their left edge and the left wall...
+    pub spawn_left_gap: usize,
+    // ...and this many empty rows between their bottom edge and the top of the tower.
+    pub spawn_height_gap: usize,
+    // Dropped in order, starting again from the first after the last.
+    pub rock_shapes: Vec<RockShape>,
+}
+
+impl Default for ChamberConfig {
+    fn default() -> Self {
+        Self {
+            width: 7,
+            spawn_left_gap: 2,
+            spawn_height_gap: 3,
+            rock_shapes: RockShape::parse_all(DEFAULT_ROCK_SHAPES).unwrap(),
+        }
+    }
+}
+
+impl ChamberConfig {
+    pub const MAX_SPAWN_HEIGHT_GAP: usize = 1000;
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.width == 0 || self.width > Row::BITS as usize {
+            return Err(format!(
+                "the chamber has to be between 1 and {} wide",
+                Row::BITS
+            ));
+        }
+        // Rocks fall one row at a time, so a much bigger gap would only be slow.
+        if self.spawn_height_gap > Self::MAX_SPAWN_HEIGHT_GAP {
+            return Err(format!(
+                "rocks have to appear at most {} rows above the tower",
+                Self::MAX_SPAWN_HEIGHT_GAP
+            ));
+        }
+        if self.rock_shapes.is_empty() {
+            return Err("expected at least one rock shape".to_owned());
+        }
+        if let Some(shape) = self
+            .rock_shapes
+            .iter()
+            .find(|shape| self.spawn_left_gap + shape.width() > self.width)
+        {
+            return Err(format!(
+                "a rock {} wide doesn't fit in a chamber {} wide when it appears {} from the wall",
+                shape.width(),
+                self.width,
+                self.spawn_left_gap
+            ));
+        }
+        Ok(())
+    }
+
+    fn full_row(&self) -> Row {
+        Row::MAX >> (Row::BITS as usize - self.width)
+    }
+}
+
+#[derive(Clone)]
+pub struct Rock {
+    // Bottom row first, already shifted to the rock's column.
+    rows: Vec<Row>,
+    bottom: i64,
+}
+
+impl Rock {
+    pub fn new(rock_shape: &RockShape, config: &ChamberConfig, fallen_rocks: &FallenRocks) -> Self {
+        Self {
+            rows: rock_shape
+                .rows
+                .iter()
+                .map(|row| row << config.spawn_left_gap)
+                .collect(),
+            bottom: fallen_rocks.max_height + config.spawn_height_gap as i64 + 1,
+        }
+    }
+
+    // Returns whether the rock moved.
+    pub fn blow(&mut self, direction: WindDirection, fallen_rocks: &FallenRocks) -> bool {
+        let (edge, shift): (Row, fn(Row) -> Row) = match direction {
+            WindDirection::Left => (1, |row| row >> 1),
+            WindDirection::Right => (1 << (fallen_rocks.width - 1), |row| row << 1),
+        };
+        if self.rows.iter().any(|row| row & edge != 0) {
+            return false;
+        }
+
+        let shifted_rows: Vec<_> = self.rows.iter().map(|row| shift(*row)).collect();
+        let can_shift = !fallen_rocks.collides(&shifted_rows, self.bottom);
+        if can_shift {
+            self.rows = shifted_rows;
+        }
+        can_shift
+    }
+
+    pub fn drop(&mut self, fallen_rocks: &mut FallenRocks) -> bool {
+        let can_drop = !fallen_rocks.collides(&self.rows, self.bottom - 1);
+        if can_drop {
+            self.bottom -= 1;
+        }
+        can_drop
+    }
+
+    pub fn fall(
+        &mut self,
+        fallen_rocks: &mut FallenRocks,
+        wind: &mut impl Iterator<Item = WindDirection>,
+    ) {
+        loop {
+            self.blow(wind.next().unwrap(), fallen_rocks);
+            if !self.drop(fallen_rocks) {
+                fallen_rocks.add_rock(self);
+                break;
+            }
+        }
+    }
+}
+
+pub struct FallenRocks {
+    width: usize,
+    full_row: Row,
+    // rows[0] is the row at y = pruned_height + 1. Rows at or below pruned_height, including the
+    // floor at y = 0, can't be reached by a falling rock any more and count as filled.
+    rows: Vec<Row>,
+    pruned_height: i64,
+    pub max_height: i64,
+}
+
+impl FallenRocks {
+    pub fn new(config: &ChamberConfig) -> Self {
+        Self {
+            width: config.width,
+            full_row: config.full_row(),
+            rows: vec![],
+            pruned_height: 0,
+            max_height: 0,
+        }
+    }
+
+    fn row(&self, y: i64) -> Row {
+        if y <= self.pruned_height {
+            self.full_row
+        } else {
+            let idx = (y - self.pruned_height - 1) as usize;
+            self.rows.get(idx).cloned().unwrap_or(0)
+        }
+    }
+
+    fn collides(&self, rock_rows: &[Row], bottom: i64) -> bool {
+        rock_rows
+            .iter()
+            .zip(bottom..)
+            .any(|(rock_row, y)| rock_row & self.row(y) != 0)
+    }
+
+    pub fn add_rock(&mut self, rock: &Rock) {
+        for (rock_row, y) in rock.rows.iter().zip(rock.bottom..) {
+            let idx = (y - self.pruned_height - 1) as usize;
+            if idx >= self.rows.len() {
+                self.rows.resize(idx + 1, 0);
+            }
+            self.rows[idx] |= rock_row;
+        }
+        self.max_height = self.pruned_height + self.rows.len() as i64;
+        self.prune();
+    }
+
+    // Falling rocks only move down and sideways, so the cells they can reach are found by
+    // spreading down and sideways from the open space above the tower. Everything below the lowest
+    // reachable row can be forgotten.
+    fn prune(&mut self) {
+        let mut reachable = self.full_row;
+        let mut lowest_reachable_idx = self.rows.len();
+        for idx in (0..self.rows.len()).rev() {
+            let open = !self.rows[idx] & self.full_row;
+            reachable &= open;
+            loop {
+                let spread = (reachable | reachable << 1 | reachable >> 1) & open;
+                if spread == reachable {
+                    break;
+                }
+                reachable = spread;
+            }
+            if reachable == 0 {
+                break;
+            }
+            lowest_reachable_idx = idx;
+        }
+        self.rows.drain(..lowest_reachable_idx);
+        self.pruned_height += lowest_reachable_idx as i64;
+    }
+
+    // Everything that can still affect where rocks land, relative to the top of the tower.
+    pub fn surface_profile(&self) -> Vec<Row> {
+        self.rows.clone()
+    }
+}
+
+impl FallenRocks {
+    // Draws the chamber like the puzzle does, with the falling rock as @. If `top_rows` is given,
+    // only that many rows from the top are drawn.
+    pub fn render(&self, falling_rock: Option<&Rock>, top_rows: Option<usize>) -> String {
+        let top = falling_rock.map_or(self.max_height, |rock| {
+            i64::max(self.max_height, rock.bottom + rock.rows.len() as i64 - 1)
+        });
+        let mut bottom = self.pruned_height + 1;
+        if let Some(top_rows) = top_rows {
+            bottom = i64::max(bottom, top - top_rows as i64 + 1);
+        }
+
+        let mut result = String::new();
+        for y in (bottom..=top).rev() {
+            let row = self.row(y);
+            let rock_row = falling_rock
+                .and_then(|rock| rock.rows.get(usize::try_from(y - rock.bottom).ok()?))
+                .cloned()
+                .unwrap_or(0);
+            result.push('|');
+            for x in 0..self.width {
+                result.push(match (rock_row & (1 << x) != 0, row & (1 << x) != 0) {
+                    (true, _) => '@',
+                    (false, true) => '#',
+                    (false, false) => '.',
+                });
+            }
+            result.push_str("|\n");
+        }
+        // When the floor isn't shown, because it's been pruned or is below the rows drawn, the
+        // bottom edge is drawn as ~ to show there's more tower below it.
+        let floor = if bottom == 1 { '-' } else { '~' };
+        result.push('+');
+        result.extend(std::iter::repeat_n(floor, self.width));
+        result.push_str("+\n");
+        result
+    }
+}
+
+impl Debug for FallenRocks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}", self.render(None, None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChamberConfig, RockShape};
+
+    #[test]
+    fn default_shapes() {
+        let shapes = ChamberConfig::default().rock_shapes;
+        let sizes: Vec<_> = shapes
+            .iter()
+            .map(|shape| (shape.width(), shape.height()))
+            .collect();
+        assert_eq!(sizes, vec![(4, 1), (3, 3), (3, 3), (1, 4), (2, 2)]);
+        // The L is drawn top row first, so its bottom row is the long one.
+        assert_eq!(shapes[2].rows, vec![0b111, 0b100, 0b100]);
+    }
+
+    #[test]
+    fn shapes_have_to_fit() {
+        let config = ChamberConfig {
+            width: 5,
+            ..ChamberConfig::default()
+        };
+        assert!(config.validate().is_err());
+        let config = ChamberConfig {
+            spawn_height_gap: ChamberConfig::MAX_SPAWN_HEIGHT_GAP + 1,
+            ..ChamberConfig::default()
+        };
+        assert!(config.validate().is_err());
+        assert!(RockShape::parse_all("#x#").is_err());
+        let padded = RockShape::parse_all("...\n.#.\n.##\n").unwrap();
+        assert_eq!(padded, RockShape::parse_all("#.\n##").unwrap());
+    }
+}
@@ -0,0 +1,273 @@
+mod chamber;
+mod trace;
+
+use std::{collections::HashMap, fmt::Display};
+
+pub use chamber::{ChamberConfig, RockShape};
+use chamber::{FallenRocks, Rock};
+pub use trace::{write_trace, TraceOptions};
+use utils::{numbered_lines, ParseError, Solution};
+
+#[derive(Clone, Copy)]
+pub enum WindDirection {
+    Left,
+    Right,
+}
+
+// Hands out the jets in order, over and over, keeping track of where in the pattern it is.
+struct Wind<'a> {
+    jets: &'a [WindDirection],
+    next_idx: usize,
+}
+
+impl<'a> Wind<'a> {
+    fn new(jets: &'a [WindDirection]) -> Self {
+        Self { jets, next_idx: 0 }
+    }
+}
+
+impl Iterator for Wind<'_> {
+    type Item = WindDirection;
+
+    fn next(&mut self) -> Option<WindDirection> {
+        let direction = self.jets[self.next_idx];
+        self.next_idx = (self.next_idx + 1) % self.jets.len();
+        Some(direction)
+    }
+}
+
+pub fn tower_height(config: &ChamberConfig, jets: &[WindDirection], rock_count: usize) -> i64 {
+    let repeated_rock_shapes = config.rock_shapes.iter().cycle();
+    let mut wind = Wind::new(jets);
+
+    let mut fallen_rocks = FallenRocks::new(config);
+    for rock_shape in repeated_rock_shapes.take(rock_count) {
+        let mut rock = Rock::new(rock_shape, config, &fallen_rocks);
+        rock.fall(&mut fallen_rocks, &mut wind);
+    }
+    fallen_rocks.max_height
+}
+
+pub const PART_1_ROCK_COUNT: usize = 2022;
+pub const PART_2_ROCK_COUNT: usize = 1_000_000_000_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    // The number of rocks dropped before the tower starts repeating.
+    pub start: usize,
+    pub length: usize,
+    pub height_gain: i64,
+}
+
+pub struct TowerHeight {
+    pub height: i64,
+    // None if the rocks ran out before the tower started repeating.
+    pub cycle: Option<Cycle>,
+}
+
+// Once each rock shape has met each jet this many times, the tower's taken to never repeat.
+const CYCLE_SEARCH_ROUNDS: usize = 10;
+
+// The tower hadn't repeated after `rocks_dropped` rocks, and there were too many to drop them all.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NoCycle {
+    pub rocks_dropped: usize,
+}
+
+impl Display for NoCycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the tower didn't repeat in the first {} rocks",
+            self.rocks_dropped
+        )
+    }
+}
+
+// Part 2's answer, if the tower repeats.
+pub struct ExtrapolatedHeight(pub Result<TowerHeight, NoCycle>);
+
+impl Display for ExtrapolatedHeight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Ok(tower) => write!(f, "{tower}"),
+            Err(err) => write!(f, "none, since {err}"),
+        }
+    }
+}
+
+impl Display for TowerHeight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.height)
+    }
+}
+
+// Drops rocks until the state after one lands repeats: the same rock shape and jet come next, and
+// the top of the tower has the same shape. From then on the tower grows the same way every cycle.
+// If it hasn't repeated after a few times through every rock shape and jet, it gives up rather
+// than dropping a huge number of rocks one by one.
+pub fn extrapolated_tower_height(
+    config: &ChamberConfig,
+    jets: &[WindDirection],
+    rock_count: usize,
+) -> Result<TowerHeight, NoCycle> {
+    let mut wind = Wind::new(jets);
+    let mut fallen_rocks = FallenRocks::new(config);
+    let mut heights = vec![0];
+    let mut seen_states = HashMap::new();
+
+    let search_limit = CYCLE_SEARCH_ROUNDS * config.rock_shapes.len() * jets.len();
+    let repeated_rock_shapes = config.rock_shapes.iter().cycle();
+    for (rock_idx, rock_shape) in repeated_rock_shapes.enumerate().take(rock_count) {
+        if rock_idx == search_limit {
+            return Err(NoCycle {
+                rocks_dropped: rock_idx,
+            });
+        }
+        let mut rock = Rock::new(rock_shape, config, &fallen_rocks);
+        rock.fall(&mut fallen_rocks, &mut wind);
+        let rocks_dropped = rock_idx + 1;
+        heights.push(fallen_rocks.max_height);
+
+        let state = (
+            rocks_dropped % config.rock_shapes.len(),
+            wind.next_idx,
+            fallen_rocks.surface_profile(),
+        );
+        if let Some(start) = seen_states.insert(state, rocks_dropped) {
+            let cycle = Cycle {
+                start,
+                length: rocks_dropped - start,
+                height_gain: heights[rocks_dropped] - heights[start],
+            };
+            let cycle_count = (rock_count - start) / cycle.length;
+            let remainder = (rock_count - start) % cycle.length;
+            return Ok(TowerHeight {
+                height: heights[start + remainder] + cycle_count as i64 * cycle.height_gain,
+                cycle: Some(cycle),
+            });
+        }
+    }
+    Ok(TowerHeight {
+        height: fallen_rocks.max_height,
+        cycle: None,
+    })
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    const INPUT_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Vec<WindDirection>;
+    type Part1 = i64;
+    type Part2 = ExtrapolatedHeight;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut jets = vec![];
+        for line in numbered_lines(input) {
+            for (idx, ch) in line.text.char_indices() {
+                jets.push(match ch {
+                    '<' => WindDirection::Left,
+                    '>' => WindDirection::Right,
+                    _ => return Err(line.error(&line.text[idx..idx + ch.len_utf8()], "< or >")),
+                });
+            }
+        }
+        if jets.is_empty() {
+            return Err(ParseError::end_of_input("at least one jet"));
+        }
+        Ok(jets)
+    }
+
+    fn part1(jets: &Self::Input) -> i64 {
+        tower_height(&ChamberConfig::default(), jets, PART_1_ROCK_COUNT)
+    }
+
+    fn part2(jets: &Self::Input) -> ExtrapolatedHeight {
+        ExtrapolatedHeight(extrapolated_tower_height(
+            &ChamberConfig::default(),
+            jets,
+            PART_2_ROCK_COUNT,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::{check_answers, read_input, InputSource, Solution};
+
+    use super::{
+        extrapolated_tower_height, tower_height, write_trace, ChamberConfig, Day17, NoCycle,
+        TraceOptions, PART_2_ROCK_COUNT,
+    };
+
+    #[test]
+    fn example() {
+        check_answers::<Day17>("example");
+    }
+
+    #[test]
+    fn input() {
+        check_answers::<Day17>("input");
+    }
+
+    #[test]
+    fn trace_matches_puzzle() {
+        let input = read_input(
+            Day17::INPUT_DIR,
+            &InputSource::Variant("example".to_owned()),
+        );
+        let jets = Day17::parse(&input.unwrap()).unwrap();
+        let options = TraceOptions {
+            rock_count: 2,
+            top_rows: None,
+        };
+        let mut trace = vec![];
+        write_trace(&ChamberConfig::default(), &jets, &options, &mut trace).unwrap();
+        let trace = String::from_utf8(trace).unwrap();
+        let frames: Vec<_> = trace.split("\n\n").collect();
+        assert_eq!(
+            frames[1],
+            "Jet of gas pushes rock right:\n|...@@@@|\n|.......|\n|.......|\n|.......|\n+-------+"
+        );
+        assert_eq!(
+            frames[8],
+            "Rock falls 1 unit, causing it to come to rest:\n|..####.|\n+-------+"
+        );
+        assert_eq!(
+            frames[9],
+            "A new rock begins falling:\n|...@...|\n|..@@@..|\n|...@...|\n|.......|\n|.......|\n|.......|\n|..####.|\n+-------+"
+        );
+    }
+
+    #[test]
+    fn extrapolation_matches_simulation() {
+        let input = read_input(Day17::INPUT_DIR, &InputSource::default()).unwrap();
+        let jets = Day17::parse(&input).unwrap();
+        let config = ChamberConfig::default();
+        let extrapolated = extrapolated_tower_height(&config, &jets, 100_000).unwrap();
+        assert!(extrapolated
+            .cycle
+            .is_some_and(|cycle| cycle.start + cycle.length < 100_000));
+        assert_eq!(extrapolated.height, tower_height(&config, &jets, 100_000));
+    }
+
+    // Everything is blown against the right wall, leaving the floor on the left in reach for ever.
+    #[test]
+    fn gives_up_on_a_tower_that_never_repeats() {
+        let config = ChamberConfig {
+            width: 32,
+            ..ChamberConfig::default()
+        };
+        let jets = Day17::parse(">").unwrap();
+        assert_eq!(
+            extrapolated_tower_height(&config, &jets, PART_2_ROCK_COUNT).err(),
+            Some(NoCycle { rocks_dropped: 50 })
+        );
+        let tower = extrapolated_tower_height(&config, &jets, 50).unwrap();
+        assert_eq!(tower.height, tower_height(&config, &jets, 50));
+        assert!(tower.cycle.is_none());
+    }
+}
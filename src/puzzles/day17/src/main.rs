@@ -1,149 +1,116 @@
-use std::{collections::HashSet, fmt::Debug};
-
-enum WindDirection {
-    Left,
-    Right,
-}
-
-#[derive(Clone)]
-struct Rock {
-    parts: HashSet<(i64, i64)>,
-}
-
-impl Rock {
-    fn new(rock_shape: &[(i64, i64)], fallen_rocks: &FallenRocks) -> Self {
-        let parts = rock_shape
-            .iter()
-            .map(|(x, y)| (x + 2, y + fallen_rocks.max_height + 4))
-            .collect();
-
-        Self { parts }
-    }
-
-    fn blow(&mut self, direction: WindDirection, fallen_rocks: &FallenRocks) {
-        let shift = match direction {
-            WindDirection::Left => -1,
-            WindDirection::Right => 1,
-        };
-
-        let shifted_parts: HashSet<_> = self
-            .parts
-            .iter()
-            .cloned()
-            .map(|(x, y)| (x + shift, y))
-            .collect();
-
-        let can_shift = shifted_parts.iter().all(|(x, _)| *x >= 0 && *x < 7)
-            && shifted_parts.intersection(&fallen_rocks.parts).count() == 0;
-
-        if can_shift {
-            self.parts = shifted_parts;
-        }
-    }
-
-    fn drop(&mut self, fallen_rocks: &mut FallenRocks) -> bool {
-        let dropped_parts: HashSet<_> = self
-            .parts
-            .iter()
-            .cloned()
-            .map(|(x, y)| (x, y - 1))
-            .collect();
-        let can_drop = dropped_parts.iter().all(|(_, y)| *y > 0)
-            && dropped_parts.intersection(&fallen_rocks.parts).count() == 0;
-
-        if can_drop {
-            self.parts = dropped_parts;
+use std::{
+    env,
+    fs::{read_to_string, File},
+    io::{self, BufWriter, Write},
+};
+
+use day17::{
+    extrapolated_tower_height, tower_height, write_trace, ChamberConfig, Day17, RockShape,
+    TraceOptions, PART_1_ROCK_COUNT, PART_2_ROCK_COUNT,
+};
+use utils::{exit_with_error, take_flag, take_number, take_value};
+
+// Returns None if none of the chamber options were given.
+fn chamber_config(args: &mut Vec<String>) -> Result<Option<ChamberConfig>, String> {
+    let width = take_number(args, "--width")?;
+    let spawn_left_gap = take_number(args, "--spawn-left-gap")?;
+    let spawn_height_gap = take_number(args, "--spawn-height-gap")?;
+    let rock_shapes = match take_value(args, "--shapes")? {
+        Some(path) => {
+            let shapes = read_to_string(&path)
+                .map_err(|err| format!("failed to read rock shapes from {path}: {err}"))?;
+            let shapes = RockShape::parse_all(&shapes)
+                .map_err(|err| format!("failed to parse rock shapes from {path}: {err}"))?;
+            Some(shapes)
         }
-        can_drop
+        None => None,
+    };
+    if width.is_none()
+        && spawn_left_gap.is_none()
+        && spawn_height_gap.is_none()
+        && rock_shapes.is_none()
+    {
+        return Ok(None);
     }
 
-    fn fall(
-        &mut self,
-        fallen_rocks: &mut FallenRocks,
-        wind: &mut impl Iterator<Item = WindDirection>,
-    ) {
-        loop {
-            self.blow(wind.next().unwrap(), fallen_rocks);
-            if !self.drop(fallen_rocks) {
-                fallen_rocks.add_rock(self);
-                break;
-            }
-        }
-    }
+    let default = ChamberConfig::default();
+    let config = ChamberConfig {
+        width: width.unwrap_or(default.width),
+        spawn_left_gap: spawn_left_gap.unwrap_or(default.spawn_left_gap),
+        spawn_height_gap: spawn_height_gap.unwrap_or(default.spawn_height_gap),
+        rock_shapes: rock_shapes.unwrap_or(default.rock_shapes),
+    };
+    config.validate()?;
+    Ok(Some(config))
 }
 
-struct FallenRocks {
-    parts: HashSet<(i64, i64)>,
-    max_height: i64,
-}
-
-impl FallenRocks {
-    fn new() -> Self {
-        Self {
-            max_height: 0,
-            parts: HashSet::new(),
-        }
-    }
-
-    fn add_rock(&mut self, rock: &Rock) {
-        self.parts = self.parts.union(&rock.parts).cloned().collect();
-        self.max_height = i64::max(
-            self.max_height,
-            rock.parts.iter().map(|(_, y)| *y).max().unwrap(),
-        );
-    }
+// Returns None unless --trace was given. The trace goes to stdout unless there's a --trace-output.
+fn trace_options(args: &mut Vec<String>) -> Result<Option<(TraceOptions, Option<String>)>, String> {
+    let Some(rock_count) = take_number(args, "--trace")? else {
+        return Ok(None);
+    };
+    let top_rows = take_number(args, "--trace-rows")?;
+    let output = take_value(args, "--trace-output")?;
+    Ok(Some((
+        TraceOptions {
+            rock_count,
+            top_rows,
+        },
+        output,
+    )))
 }
 
-impl Debug for FallenRocks {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut result = String::new();
-        for row_idx in (0..=self.max_height).rev() {
-            for col_idx in 0..=8 {
-                if row_idx == 0 {
-                    if col_idx == 0 || col_idx == 8 {
-                        result.push('+');
-                    } else {
-                        result.push('-');
-                    }
-                } else if col_idx == 0 || col_idx == 8 {
-                    result.push('|');
-                } else if self.parts.contains(&(col_idx - 1, row_idx)) {
-                    result.push('#');
-                } else {
-                    result.push('.');
-                }
-            }
-            result.push('\n');
+fn trace(
+    config: &ChamberConfig,
+    args: Vec<String>,
+    options: &TraceOptions,
+    output: Option<String>,
+) -> Result<(), String> {
+    let jets = utils::load_input::<Day17>(args.into_iter());
+    let mut out: Box<dyn Write> = match &output {
+        Some(path) => {
+            let file =
+                File::create(path).map_err(|err| format!("failed to create {path}: {err}"))?;
+            Box::new(BufWriter::new(file))
         }
-        result.push('\n');
-        f.write_str(&result)
-    }
+        None => Box::new(io::stdout().lock()),
+    };
+    write_trace(config, &jets, options, &mut out)
+        .and_then(|_| out.flush())
+        .map_err(|err| format!("failed to write the trace: {err}"))
 }
 
 fn main() {
-    let rock_shapes = vec![
-        vec![(0, 0), (1, 0), (2, 0), (3, 0)],
-        vec![(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
-        vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
-        vec![(0, 0), (0, 1), (0, 2), (0, 3)],
-        vec![(0, 0), (1, 0), (0, 1), (1, 1)],
-    ];
-    let repeated_rock_shapes = rock_shapes.iter().cycle();
-    let mut wind = include_str!("../input.txt")
-        .chars()
-        .cycle()
-        .map(|ch| match ch {
-            '<' => WindDirection::Left,
-            '>' => WindDirection::Right,
-            _ => panic!("unexpected wind char"),
-        });
-
-    let mut fallen_rocks = FallenRocks::new();
-    for rock_shape in repeated_rock_shapes.take(2022) {
-        let mut rock = Rock::new(rock_shape, &fallen_rocks);
-        rock.fall(&mut fallen_rocks, &mut wind);
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let show_cycle = take_flag(&mut args, "--cycle");
+    let trace_options = trace_options(&mut args).unwrap_or_else(|message| exit_with_error(message));
+    let config = chamber_config(&mut args).unwrap_or_else(|message| exit_with_error(message));
+
+    if let Some((options, output)) = trace_options {
+        let config = config.unwrap_or_default();
+        return trace(&config, args, &options, output)
+            .unwrap_or_else(|message| exit_with_error(message));
+    }
+    if !show_cycle && config.is_none() {
+        return utils::run_with_args::<Day17>(args.into_iter());
     }
 
-    let part_1_answer = fallen_rocks.max_height;
-    println!("part 1: {part_1_answer}");
+    let config = config.unwrap_or_default();
+    let jets = utils::load_input::<Day17>(args.into_iter());
+    println!(
+        "height after {PART_1_ROCK_COUNT} rocks: {}",
+        tower_height(&config, &jets, PART_1_ROCK_COUNT)
+    );
+    let tower = extrapolated_tower_height(&config, &jets, PART_2_ROCK_COUNT)
+        .unwrap_or_else(|err| exit_with_error(err));
+    if show_cycle {
+        match tower.cycle {
+            Some(cycle) => println!(
+                "after {} rocks the tower repeats every {} rocks, growing {} rows each time",
+                cycle.start, cycle.length, cycle.height_gain
+            ),
+            None => println!("the tower didn't repeat"),
+        }
+    }
+    println!("height after {PART_2_ROCK_COUNT} rocks: {}", tower.height);
 }
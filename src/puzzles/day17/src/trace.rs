@@ -0,0 +1,79 @@
+use std::io::{self, Write};
+
+use crate::{
+    chamber::{FallenRocks, Rock},
+    ChamberConfig, Wind, WindDirection,
+};
+
+pub struct TraceOptions {
+    pub rock_count: usize,
+    // Only draw this many rows from the top of the chamber, so tall towers stay readable.
+    pub top_rows: Option<usize>,
+}
+
+fn write_frame(
+    out: &mut impl Write,
+    caption: &str,
+    fallen_rocks: &FallenRocks,
+    falling_rock: Option<&Rock>,
+    options: &TraceOptions,
+) -> io::Result<()> {
+    writeln!(out, "{caption}")?;
+    writeln!(
+        out,
+        "{}",
+        fallen_rocks.render(falling_rock, options.top_rows)
+    )
+}
+
+// Writes the chamber after every jet push and every fall, with the captions the puzzle uses.
+pub fn write_trace(
+    config: &ChamberConfig,
+    jets: &[WindDirection],
+    options: &TraceOptions,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let mut wind = Wind::new(jets);
+    let mut fallen_rocks = FallenRocks::new(config);
+    let repeated_rock_shapes = config.rock_shapes.iter().cycle();
+
+    for (rock_idx, rock_shape) in repeated_rock_shapes.enumerate().take(options.rock_count) {
+        let mut rock = Rock::new(rock_shape, config, &fallen_rocks);
+        let caption = if rock_idx == 0 {
+            "The first rock begins falling:"
+        } else {
+            "A new rock begins falling:"
+        };
+        write_frame(out, caption, &fallen_rocks, Some(&rock), options)?;
+
+        loop {
+            let direction = wind.next().unwrap();
+            let direction_name = match direction {
+                WindDirection::Left => "left",
+                WindDirection::Right => "right",
+            };
+            let caption = if rock.blow(direction, &fallen_rocks) {
+                format!("Jet of gas pushes rock {direction_name}:")
+            } else {
+                format!("Jet of gas pushes rock {direction_name}, but nothing happens:")
+            };
+            write_frame(out, &caption, &fallen_rocks, Some(&rock), options)?;
+
+            if rock.drop(&mut fallen_rocks) {
+                write_frame(
+                    out,
+                    "Rock falls 1 unit:",
+                    &fallen_rocks,
+                    Some(&rock),
+                    options,
+                )?;
+            } else {
+                fallen_rocks.add_rock(&rock);
+                let caption = "Rock falls 1 unit, causing it to come to rest:";
+                write_frame(out, caption, &fallen_rocks, None, options)?;
+                break;
+            }
+        }
+    }
+    Ok(())
+}
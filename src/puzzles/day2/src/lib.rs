@@ -0,0 +1,143 @@
+use utils::{parse_lines, Line, ParseError, Solution};
+
+#[derive(Clone, PartialEq)]
+pub enum Shape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Shape {
+    fn from_char(s: char) -> Option<Self> {
+        match s {
+            'A' | 'X' => Some(Shape::Rock),
+            'B' | 'Y' => Some(Shape::Paper),
+            'C' | 'Z' => Some(Shape::Scissors),
+            _ => None,
+        }
+    }
+
+    fn winning_shape_against(other: &Self) -> Self {
+        match other {
+            Shape::Rock => Shape::Paper,
+            Shape::Paper => Shape::Scissors,
+            Shape::Scissors => Shape::Rock,
+        }
+    }
+
+    fn losing_shape_against(other: &Self) -> Self {
+        match other {
+            Shape::Rock => Shape::Scissors,
+            Shape::Paper => Shape::Rock,
+            Shape::Scissors => Shape::Paper,
+        }
+    }
+
+    fn shape_score(&self) -> u32 {
+        match self {
+            Shape::Rock => 1,
+            Shape::Paper => 2,
+            Shape::Scissors => 3,
+        }
+    }
+
+    fn outcome_score_against(&self, other: &Self) -> u32 {
+        if self == other {
+            3
+        } else if self == &Self::winning_shape_against(other) {
+            6
+        } else {
+            0
+        }
+    }
+}
+
+fn parse_shape(line: &Line, token: &str, expected: &str) -> Result<Shape, ParseError> {
+    let mut chars = token.chars();
+    match (chars.next().and_then(Shape::from_char), chars.next()) {
+        (Some(shape), None) => Ok(shape),
+        _ => Err(line.error(token, expected)),
+    }
+}
+
+// The second column is read as a shape here, which is what it means in part 1. Part 2 reinterprets
+// it as the outcome we want.
+fn parse_game(line: Line) -> Result<(Shape, Shape), ParseError> {
+    let tokens = line.tokens();
+    let their_shape = parse_shape(&line, tokens.get(0, "A, B or C")?, "A, B or C")?;
+    let my_shape = parse_shape(&line, tokens.get(1, "X, Y or Z")?, "X, Y or Z")?;
+    if let Some(extra) = tokens.rest(2).first() {
+        return Err(line.error(extra, "end of line"));
+    }
+    Ok((their_shape, my_shape))
+}
+
+fn part_1_game_score((their_shape, my_shape): &(Shape, Shape)) -> u32 {
+    my_shape.shape_score() + my_shape.outcome_score_against(their_shape)
+}
+
+fn part_2_game_score((their_shape, outcome): &(Shape, Shape)) -> u32 {
+    let my_shape = match outcome {
+        Shape::Rock => Shape::losing_shape_against(their_shape),
+        Shape::Paper => their_shape.clone(),
+        Shape::Scissors => Shape::winning_shape_against(their_shape),
+    };
+    my_shape.shape_score() + my_shape.outcome_score_against(their_shape)
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    const INPUT_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Vec<(Shape, Shape)>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, parse_game)
+    }
+
+    fn part1(games: &Self::Input) -> u32 {
+        games.iter().map(part_1_game_score).sum::<u32>()
+    }
+
+    fn part2(games: &Self::Input) -> u32 {
+        games.iter().map(part_2_game_score).sum::<u32>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::{check_answers, Solution};
+
+    use super::Day2;
+
+    #[test]
+    fn example() {
+        check_answers::<Day2>("example");
+    }
+
+    #[test]
+    fn input() {
+        check_answers::<Day2>("input");
+    }
+
+    #[test]
+    fn malformed_games() {
+        let err = Day2::parse("A X\nB Q\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 3, "Q"));
+        assert_eq!(err.expected, "X, Y or Z");
+
+        let err = Day2::parse("AB X\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.found.as_str()), (1, 1, "AB"));
+        assert_eq!(err.expected, "A, B or C");
+
+        let err = Day2::parse("C\n").err().unwrap();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 2, "X, Y or Z")
+        );
+    }
+}
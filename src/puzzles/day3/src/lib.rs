@@ -0,0 +1,94 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+use utils::{parse_lines, Line, ParseError, Solution};
+
+fn item_priority(item: char) -> u32 {
+    match item {
+        'a'..='z' => (item as u32) - 96,
+        'A'..='Z' => (item as u32) - 38,
+        _ => panic!("unexpected input"),
+    }
+}
+
+fn parse_rucksack(line: Line) -> Result<String, ParseError> {
+    if let Some((idx, _)) = line
+        .text
+        .char_indices()
+        .find(|(_, item)| !item.is_ascii_alphabetic())
+    {
+        return Err(line.error(&line.text[idx..idx + 1], "an item letter"));
+    }
+    if !line.text.len().is_multiple_of(2) {
+        return Err(line.error_at_end("an even number of items"));
+    }
+    Ok(line.text.to_owned())
+}
+
+fn single_common_character<'a>(strings: impl Iterator<Item = &'a str>) -> char {
+    let sets = strings.map(|s| HashSet::from_iter(s.chars()));
+    let common_chars: HashSet<_> = sets
+        .reduce(|acc, set| HashSet::from_iter(acc.intersection(&set).cloned()))
+        .unwrap_or_else(|| panic!("unexpected empty group of sets"));
+    if common_chars.len() > 1 {
+        panic!("found multiple common characters");
+    }
+    common_chars
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| panic!("found no common character"))
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    const INPUT_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Vec<String>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, parse_rucksack)
+    }
+
+    fn part1(rucksacks: &Self::Input) -> u32 {
+        rucksacks
+            .iter()
+            .map(|line| {
+                let (first_compartment, second_compartment) = line.split_at(line.len() / 2);
+                let misplaced_item = single_common_character(
+                    vec![first_compartment, second_compartment].into_iter(),
+                );
+                item_priority(misplaced_item)
+            })
+            .sum::<u32>()
+    }
+
+    fn part2(rucksacks: &Self::Input) -> u32 {
+        rucksacks
+            .iter()
+            .chunks(3)
+            .into_iter()
+            .map(|elf_group| item_priority(single_common_character(elf_group.map(String::as_str))))
+            .sum::<u32>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::check_answers;
+
+    use super::Day3;
+
+    #[test]
+    fn example() {
+        check_answers::<Day3>("example");
+    }
+
+    #[test]
+    fn input() {
+        check_answers::<Day3>("input");
+    }
+}
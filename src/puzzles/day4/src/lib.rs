@@ -0,0 +1,89 @@
+use std::ops::RangeInclusive;
+
+use utils::{parse_lines, Line, ParseError, Solution};
+
+pub struct SectionIdRange {
+    range: RangeInclusive<u32>,
+}
+
+impl SectionIdRange {
+    fn fully_contains(&self, other: &SectionIdRange) -> bool {
+        self.range.contains(other.range.start()) && self.range.contains(other.range.end())
+    }
+    fn contains_either_end(&self, other: &SectionIdRange) -> bool {
+        self.range.contains(other.range.start()) || self.range.contains(other.range.end())
+    }
+}
+
+fn parse_range(line: &Line, range_str: &str) -> Result<SectionIdRange, ParseError> {
+    let (first, second) = range_str
+        .split_once('-')
+        .ok_or_else(|| line.error(range_str, "a range like 2-4"))?;
+    let first = line.parse(first, "a section ID")?;
+    let second = line.parse(second, "a section ID")?;
+    Ok(SectionIdRange {
+        range: first..=second,
+    })
+}
+
+fn parse_pair(line: Line) -> Result<(SectionIdRange, SectionIdRange), ParseError> {
+    let (first_range_section, second_range_section) = line
+        .text
+        .split_once(',')
+        .ok_or_else(|| line.error(line.text, "two ranges separated by a comma"))?;
+    Ok((
+        parse_range(&line, first_range_section)?,
+        parse_range(&line, second_range_section)?,
+    ))
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    const INPUT_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Vec<(SectionIdRange, SectionIdRange)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, parse_pair)
+    }
+
+    fn part1(ranges: &Self::Input) -> usize {
+        ranges
+            .iter()
+            .filter(|(first_range, second_range)| {
+                first_range.fully_contains(second_range) || second_range.fully_contains(first_range)
+            })
+            .count()
+    }
+
+    fn part2(ranges: &Self::Input) -> usize {
+        ranges
+            .iter()
+            .filter(|(first_range, second_range)| {
+                first_range.contains_either_end(second_range)
+                    || second_range.contains_either_end(first_range)
+            })
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::check_answers;
+
+    use super::Day4;
+
+    #[test]
+    fn example() {
+        check_answers::<Day4>("example");
+    }
+
+    #[test]
+    fn input() {
+        check_answers::<Day4>("input");
+    }
+}
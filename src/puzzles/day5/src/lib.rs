@@ -0,0 +1,184 @@
+use itertools::Itertools;
+use std::vec;
+use utils::{numbered_lines, Line, ParseError, Solution};
+
+pub struct MoveOp {
+    count: u32,
+    source_stack: usize,
+    dest_stack: usize,
+}
+
+fn prepare_initial_stacks(lines: &[Line]) -> Result<Vec<Vec<String>>, ParseError> {
+    let mut stacks = vec![];
+    for line in lines {
+        if let Some((idx, _)) = line.text.char_indices().find(|(_, ch)| !ch.is_ascii()) {
+            return Err(line.error(&line.text[idx..], "a crate like [A] or a gap"));
+        }
+        for (stack_idx, chunk_start) in (0..line.text.len()).step_by(4).enumerate() {
+            let chunk = &line.text[chunk_start..usize::min(chunk_start + 4, line.text.len())];
+            let item = match chunk.trim_end().as_bytes() {
+                [b'[', item, b']'] if item.is_ascii_alphabetic() => Some(&chunk[1..2]),
+                [] => None,
+                _ => return Err(line.error(chunk, "a crate like [A] or a gap")),
+            };
+            if stacks.len() <= stack_idx {
+                stacks.push(vec![]);
+            }
+            if let Some(item) = item {
+                stacks[stack_idx].push(item.to_owned());
+            }
+        }
+    }
+    for stack in stacks.iter_mut() {
+        stack.reverse();
+    }
+    Ok(stacks)
+}
+
+fn parse_stack_number(line: &Line, token: &str, stack_count: usize) -> Result<usize, ParseError> {
+    let expected = format!("a stack number from 1 to {stack_count}");
+    match line.parse::<usize>(token, &expected)? {
+        stack_number @ 1.. if stack_number <= stack_count => Ok(stack_number - 1),
+        _ => Err(line.error(token, expected)),
+    }
+}
+
+fn parse_move_operation(line: &Line, stack_count: usize) -> Result<MoveOp, ParseError> {
+    let tokens = line.tokens();
+    for (idx, word) in [(0, "move"), (2, "from"), (4, "to")] {
+        let token = tokens.get(idx, &format!("{word:?}"))?;
+        if token != word {
+            return Err(line.error(token, format!("{word:?}")));
+        }
+    }
+    Ok(MoveOp {
+        count: line.parse(tokens.get(1, "a crate count")?, "a crate count")?,
+        source_stack: parse_stack_number(line, tokens.get(3, "a stack number")?, stack_count)?,
+        dest_stack: parse_stack_number(line, tokens.get(5, "a stack number")?, stack_count)?,
+    })
+}
+
+fn execute_move_operations(
+    stacks: &mut [Vec<String>],
+    move_ops: &Vec<MoveOp>,
+    move_multiple_crates_at_once: bool,
+) {
+    for move_op in move_ops {
+        if move_multiple_crates_at_once {
+            let mut vals = vec![];
+            for _ in 0..move_op.count {
+                let val = stacks[move_op.source_stack]
+                    .pop()
+                    .unwrap_or_else(|| panic!("unexpected empty stack"));
+                vals.push(val);
+            }
+            vals.reverse();
+            stacks[move_op.dest_stack].extend(vals);
+        } else {
+            for _ in 0..move_op.count {
+                let val = stacks[move_op.source_stack]
+                    .pop()
+                    .unwrap_or_else(|| panic!("unexpected empty stack"));
+                stacks[move_op.dest_stack].push(val);
+            }
+        }
+    }
+}
+
+fn top_stack_items(
+    initial_stacks: &[Vec<String>],
+    move_operations: &Vec<MoveOp>,
+    move_multiple_crates_at_once: bool,
+) -> String {
+    let mut stacks = initial_stacks.to_vec();
+    execute_move_operations(&mut stacks, move_operations, move_multiple_crates_at_once);
+    stacks
+        .iter()
+        .map(|stack| {
+            stack
+                .last()
+                .unwrap_or_else(|| panic!("unexpected empty stack"))
+        })
+        .join("")
+}
+
+pub struct Crates {
+    initial_stacks: Vec<Vec<String>>,
+    move_operations: Vec<MoveOp>,
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    const INPUT_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Crates;
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut initial_stack_lines = vec![];
+        let mut move_operation_lines = vec![];
+        for line in numbered_lines(input) {
+            if line.text.trim().starts_with('[') {
+                initial_stack_lines.push(line);
+            } else if line.text.starts_with("move") {
+                move_operation_lines.push(line);
+            }
+        }
+
+        let initial_stacks = prepare_initial_stacks(&initial_stack_lines)?;
+        let move_operations = move_operation_lines
+            .iter()
+            .map(|line| parse_move_operation(line, initial_stacks.len()))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Crates {
+            initial_stacks,
+            move_operations,
+        })
+    }
+
+    fn part1(crates: &Self::Input) -> String {
+        top_stack_items(&crates.initial_stacks, &crates.move_operations, false)
+    }
+
+    fn part2(crates: &Self::Input) -> String {
+        top_stack_items(&crates.initial_stacks, &crates.move_operations, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::{check_answers, Solution};
+
+    use super::Day5;
+
+    #[test]
+    fn example() {
+        check_answers::<Day5>("example");
+    }
+
+    #[test]
+    fn input() {
+        check_answers::<Day5>("input");
+    }
+
+    #[test]
+    fn malformed_crates_and_moves() {
+        let err = Day5::parse("[A] [B]\n 1   2\n\nmove 1 from 3 to 1\n")
+            .err()
+            .unwrap();
+        assert_eq!((err.line, err.column, err.found.as_str()), (4, 13, "3"));
+        assert_eq!(err.expected, "a stack number from 1 to 2");
+
+        let err = Day5::parse("[A] (B)\n 1   2\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.found.as_str()), (1, 5, "(B)"));
+        assert_eq!(err.expected, "a crate like [A] or a gap");
+
+        let err = Day5::parse("[A]\n 1\n\nmove 1 onto 1\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.found.as_str()), (4, 8, "onto"));
+        assert_eq!(err.expected, "\"from\"");
+    }
+}
@@ -0,0 +1,60 @@
+use itertools::Itertools;
+use utils::{numbered_lines, ParseError, Solution};
+
+fn count_chars_to_end_of_unique_window(chars: &[char], window_size: usize) -> usize {
+    chars
+        .windows(window_size)
+        .enumerate()
+        .find_map(|(idx, window)| window.iter().all_unique().then_some(idx + window_size))
+        .unwrap_or_else(|| panic!("found no unique window"))
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    const INPUT_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Vec<char>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut chars = vec![];
+        for line in numbered_lines(input) {
+            for (idx, ch) in line.text.char_indices() {
+                if !ch.is_ascii_lowercase() {
+                    let found = &line.text[idx..idx + ch.len_utf8()];
+                    return Err(line.error(found, "a lowercase letter"));
+                }
+                chars.push(ch);
+            }
+        }
+        Ok(chars)
+    }
+
+    fn part1(input_chars: &Self::Input) -> usize {
+        count_chars_to_end_of_unique_window(input_chars, 4)
+    }
+
+    fn part2(input_chars: &Self::Input) -> usize {
+        count_chars_to_end_of_unique_window(input_chars, 14)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::check_answers;
+
+    use super::Day6;
+
+    #[test]
+    fn example() {
+        check_answers::<Day6>("example");
+    }
+
+    #[test]
+    fn input() {
+        check_answers::<Day6>("input");
+    }
+}
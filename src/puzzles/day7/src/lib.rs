@@ -0,0 +1,190 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+};
+
+use utils::{numbered_lines, Line, ParseError, Solution};
+
+struct DirInfo {
+    size: Cell<u32>,
+    entries: RefCell<HashMap<String, Rc<DirInfo>>>,
+    parent: Option<Rc<DirInfo>>,
+}
+
+impl DirInfo {
+    fn add_file(&self, file_size: u32) {
+        self.size.set(self.size.get() + file_size);
+        if let Some(parent) = &self.parent {
+            parent.add_file(file_size);
+        }
+    }
+}
+
+enum ChangeDirDest<'a> {
+    Up,
+    Root,
+    ChildDir { dir_name: &'a str },
+}
+
+enum ParsedLine<'a> {
+    ChangeDir(ChangeDirDest<'a>),
+    ListDirs,
+    Directory { dir_name: &'a str },
+    File { file_size: u32 },
+}
+
+fn parse_line(line: Line<'_>) -> Result<ParsedLine<'_>, ParseError> {
+    let text = line.text;
+    let parsed_line = if text.starts_with("$ cd /") {
+        ParsedLine::ChangeDir(ChangeDirDest::Root)
+    } else if text.starts_with("$ cd ..") {
+        ParsedLine::ChangeDir(ChangeDirDest::Up)
+    } else if let Some(dir_name) = text.strip_prefix("$ cd ") {
+        ParsedLine::ChangeDir(ChangeDirDest::ChildDir { dir_name })
+    } else if text == "$ ls" {
+        ParsedLine::ListDirs
+    } else if let Some(dir_name) = text.strip_prefix("dir ") {
+        ParsedLine::Directory { dir_name }
+    } else if text.starts_with('$') {
+        return Err(line.error(text, "a cd or ls command"));
+    } else {
+        let file_size_part = text.split(' ').next().unwrap_or_default();
+        let file_size = line.parse(file_size_part, "a file size or \"dir\"")?;
+        ParsedLine::File { file_size }
+    };
+    Ok(parsed_line)
+}
+
+fn find_child_dir(parent_dir: Rc<DirInfo>, child_name: &str) -> Option<Rc<DirInfo>> {
+    let current_dir_contents = parent_dir.entries.borrow();
+    current_dir_contents.get(child_name).cloned()
+}
+
+pub struct FilesystemSummary {
+    root: Rc<DirInfo>,
+    all_dirs: Vec<Rc<DirInfo>>,
+}
+
+fn build_filesystem(input: &str) -> Result<FilesystemSummary, ParseError> {
+    let mut all_dirs = vec![];
+
+    let mut create_child_dir = |parent| {
+        let result = Rc::new(DirInfo {
+            size: Cell::new(0),
+            entries: RefCell::new(HashMap::new()),
+            parent,
+        });
+        all_dirs.push(result.clone());
+        result
+    };
+
+    let root = create_child_dir(None);
+    let mut current_dir = root.clone();
+
+    for line in numbered_lines(input) {
+        match parse_line(line)? {
+            ParsedLine::ChangeDir(ChangeDirDest::Up) => {
+                let new_dir = current_dir
+                    .parent
+                    .as_ref()
+                    .ok_or_else(|| line.error(line.text, "a directory to cd into (already at /)"))?
+                    .clone();
+                current_dir = new_dir.clone();
+            }
+            ParsedLine::ChangeDir(ChangeDirDest::Root) => {
+                current_dir = root.clone();
+            }
+            ParsedLine::ChangeDir(ChangeDirDest::ChildDir { dir_name }) => {
+                current_dir = find_child_dir(current_dir, dir_name)
+                    .ok_or_else(|| line.error(dir_name, "a directory listed by ls"))?;
+            }
+            ParsedLine::Directory { dir_name } => {
+                let new_dir = create_child_dir(Some(current_dir.clone()));
+
+                current_dir
+                    .entries
+                    .borrow_mut()
+                    .insert(dir_name.to_owned(), new_dir);
+            }
+            ParsedLine::File { file_size } => {
+                current_dir.add_file(file_size);
+            }
+            ParsedLine::ListDirs => {
+                // Don't actually need to do anything here.
+            }
+        }
+    }
+    Ok(FilesystemSummary { root, all_dirs })
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    const INPUT_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = FilesystemSummary;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        build_filesystem(input)
+    }
+
+    fn part1(filesystem: &Self::Input) -> u32 {
+        filesystem
+            .all_dirs
+            .iter()
+            .map(|dir| dir.size.get())
+            .filter(|size| *size <= 100000)
+            .sum::<u32>()
+    }
+
+    fn part2(filesystem: &Self::Input) -> u32 {
+        let total_disk_space = 70000000;
+        let required_free_space = 30000000;
+        let current_free_space = total_disk_space - filesystem.root.size.get();
+        let further_space_to_delete = required_free_space - current_free_space;
+
+        filesystem
+            .all_dirs
+            .iter()
+            .map(|dir| dir.size.get())
+            .filter(|size| *size >= further_space_to_delete)
+            .min()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::{check_answers, Solution};
+
+    use super::Day7;
+
+    #[test]
+    fn example() {
+        check_answers::<Day7>("example");
+    }
+
+    #[test]
+    fn input() {
+        check_answers::<Day7>("input");
+    }
+
+    #[test]
+    fn malformed_terminal_output() {
+        let err = Day7::parse("$ cd /\n$ rm a\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.found.as_str()), (2, 1, "$ rm a"));
+        assert_eq!(err.expected, "a cd or ls command");
+
+        let err = Day7::parse("$ cd /\n$ ls\nbig a.txt\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.found.as_str()), (3, 1, "big"));
+        assert_eq!(err.expected, "a file size or \"dir\"");
+
+        let err = Day7::parse("$ cd /\n$ ls\n$ cd a\n").err().unwrap();
+        assert_eq!((err.line, err.column, err.found.as_str()), (3, 6, "a"));
+        assert_eq!(err.expected, "a directory listed by ls");
+    }
+}
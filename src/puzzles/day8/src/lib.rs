@@ -0,0 +1,116 @@
+use utils::{Grid, ParseError, Solution};
+
+// Applies `transform` to every line of the grid read in `line_direction` (a number of clockwise
+// quarter turns), and puts the results back in the original orientation.
+fn transform_lines<T: Clone, U: Clone>(
+    grid: &Grid<T>,
+    line_direction: u32,
+    transform: impl Fn(&[T]) -> Vec<U>,
+) -> Grid<U> {
+    let turns = line_direction % 4;
+    grid.rotate(turns).map_rows(transform).rotate(4 - turns)
+}
+
+fn max_blocking_tree_heights(line: &[u32]) -> Vec<Option<u32>> {
+    let mut result = vec![];
+    let mut max = None;
+    for tree in line {
+        result.push(max);
+        max = max
+            .map(|prev_max| u32::max(*tree, prev_max))
+            .or(Some(*tree));
+    }
+    result
+}
+
+fn visibility_from_side(line_direction: u32, grid: &Grid<u32>) -> Grid<bool> {
+    transform_lines(grid, line_direction, |line| {
+        let blockers = max_blocking_tree_heights(line);
+        line.iter()
+            .enumerate()
+            .map(|(col_idx, tree)| blockers[col_idx].map(|b| *tree > b).unwrap_or(true))
+            .collect()
+    })
+}
+
+fn scenic_scores_in_direction(line_direction: u32, grid: &Grid<u32>) -> Grid<u32> {
+    transform_lines(grid, line_direction, |line| {
+        let mut latest_blocker_positions_by_height = [None; 10];
+        let mut result = vec![];
+        for (tree_idx, tree_height) in line.iter().enumerate() {
+            let score = latest_blocker_positions_by_height[*tree_height as usize..]
+                .iter()
+                .flatten()
+                .max()
+                .map(|latest_blocker_position| tree_idx - latest_blocker_position)
+                .unwrap_or(tree_idx);
+            latest_blocker_positions_by_height[*tree_height as usize] = Some(tree_idx);
+            result.push(score as u32);
+        }
+        result
+    })
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    const INPUT_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Grid<u32>;
+    type Part1 = usize;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse(input, |line, found, ch| {
+            ch.to_digit(10)
+                .ok_or_else(|| line.error(found, "a tree height"))
+        })
+    }
+
+    fn part1(grid: &Self::Input) -> usize {
+        let visibility_grid = (0..4)
+            .map(|line_direction| visibility_from_side(line_direction, grid))
+            .reduce(|overall_vis_grid, direction_vis_grid| {
+                overall_vis_grid.zip_with(&direction_vis_grid, |a, b| *a || *b)
+            })
+            .unwrap();
+
+        visibility_grid
+            .iter()
+            .filter(|(_, visible)| **visible)
+            .count()
+    }
+
+    fn part2(grid: &Self::Input) -> u32 {
+        let scenic_score_grid = (0..4)
+            .map(|line_direction| scenic_scores_in_direction(line_direction, grid))
+            .reduce(|overall_scenic_score_grid, direction_scenic_score_grid| {
+                overall_scenic_score_grid.zip_with(&direction_scenic_score_grid, |a, b| a * b)
+            })
+            .unwrap();
+
+        scenic_score_grid
+            .iter()
+            .map(|(_, score)| *score)
+            .max()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::check_answers;
+
+    use super::Day8;
+
+    #[test]
+    fn example() {
+        check_answers::<Day8>("example");
+    }
+
+    #[test]
+    fn input() {
+        check_answers::<Day8>("input");
+    }
+}
@@ -0,0 +1,103 @@
+use std::ops::{Add, Sub};
+
+use itertools::Itertools;
+use utils::{parse_lines, Line, ParseError, Solution};
+
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Vector(i32, i32);
+
+impl Add for Vector {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+fn get_next_position_for_knot(
+    current_knot_position: Vector,
+    preceding_knot_position: Vector,
+) -> Vector {
+    let difference = preceding_knot_position - current_knot_position;
+    let movement = if difference.0.abs() > 1 || difference.1.abs() > 1 {
+        Vector(difference.0.signum(), difference.1.signum())
+    } else {
+        Vector(0, 0)
+    };
+    current_knot_position + movement
+}
+
+fn parse_motion(line: Line) -> Result<(Vector, u32), ParseError> {
+    let tokens = line.tokens();
+    let direction = tokens.get(0, "U, D, L or R")?;
+    let head_direction = match direction {
+        "U" => Vector(0, -1),
+        "D" => Vector(0, 1),
+        "L" => Vector(-1, 0),
+        "R" => Vector(1, 0),
+        _ => return Err(line.error(direction, "U, D, L or R")),
+    };
+    let step_count = line.parse(tokens.get(1, "a step count")?, "a step count")?;
+    Ok((head_direction, step_count))
+}
+
+fn run_simulation(motions: &[(Vector, u32)], knot_count: usize) -> Vec<Vector> {
+    let mut knots: Vec<_> = (0..knot_count).map(|_| Vector(0, 0)).collect();
+    let mut tail_position_history = vec![knots[knots.len() - 1]];
+    for &(head_direction, step_count) in motions {
+        (0..step_count).for_each(|_| {
+            knots[0] = knots[0] + head_direction;
+            for knot_idx in 1..knot_count {
+                knots[knot_idx] = get_next_position_for_knot(knots[knot_idx], knots[knot_idx - 1]);
+            }
+            tail_position_history.push(knots[knots.len() - 1]);
+        });
+    }
+    tail_position_history
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    const INPUT_DIR: &'static str = env!("CARGO_MANIFEST_DIR");
+
+    type Input = Vec<(Vector, u32)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_lines(input, parse_motion)
+    }
+
+    fn part1(motions: &Self::Input) -> usize {
+        run_simulation(motions, 2).into_iter().unique().count()
+    }
+
+    fn part2(motions: &Self::Input) -> usize {
+        run_simulation(motions, 10).into_iter().unique().count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utils::check_answers;
+
+    use super::Day9;
+
+    #[test]
+    fn example() {
+        check_answers::<Day9>("example");
+    }
+
+    #[test]
+    fn input() {
+        check_answers::<Day9>("input");
+    }
+}
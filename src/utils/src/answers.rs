@@ -0,0 +1,73 @@
+use std::{fs::read_to_string, path::Path};
+
+use crate::{read_input, read_settings, solve, InputSource, Solution};
+
+// Answers are recorded in the same format the runner prints them in, next to the input they belong
+// to: example.txt has example.answers, input.txt has input.answers. A part can be left out of the
+// file if it hasn't been solved yet.
+pub fn parse_answers(recorded: &str) -> Vec<(u8, String)> {
+    let mut result: Vec<(u8, String)> = vec![];
+    for line in recorded.lines() {
+        let part_header = line
+            .strip_prefix("part ")
+            .and_then(|rest| rest.split_once(':'))
+            .and_then(|(part, answer)| Some((part.parse().ok()?, answer.trim())));
+        match (part_header, result.last_mut()) {
+            (Some((part, answer)), _) => result.push((part, answer.to_owned())),
+            (None, Some((_, answer))) => {
+                // Continuation of a multi-line answer like day 10's screen.
+                if !answer.is_empty() {
+                    answer.push('\n');
+                }
+                answer.push_str(line);
+            }
+            (None, None) => panic!("expected recorded answers to start with \"part 1:\""),
+        }
+    }
+    result
+}
+
+pub fn check_answers<S: Solution>(variant: &str) {
+    let answers_path = Path::new(S::INPUT_DIR).join(format!("{variant}.answers"));
+    let recorded = read_to_string(&answers_path)
+        .unwrap_or_else(|err| panic!("failed to read {}: {err}", answers_path.display()));
+    let expected = parse_answers(&recorded);
+    assert!(
+        !expected.is_empty(),
+        "no answers in {}",
+        answers_path.display()
+    );
+
+    let source = InputSource::Variant(variant.to_owned());
+    let input = read_input(S::INPUT_DIR, &source).unwrap_or_else(|err| panic!("{err}"));
+    let settings = read_settings(S::INPUT_DIR, &source).unwrap_or_else(|err| panic!("{err}"));
+    let answers = solve::<S>(&input, &settings).unwrap_or_else(|err| panic!("{err}"));
+
+    for (part, expected_answer) in expected {
+        let actual_answer = match part {
+            1 => &answers.part_1,
+            2 => &answers.part_2,
+            _ => panic!("unexpected part {part} in {}", answers_path.display()),
+        };
+        assert_eq!(
+            actual_answer.trim_end(),
+            expected_answer,
+            "day {} part {part} on {variant}",
+            S::DAY
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_answers;
+
+    #[test]
+    fn multi_line_answers() {
+        let recorded = "part 1: 13140\npart 2:\n##..\n###.\n";
+        assert_eq!(
+            parse_answers(recorded),
+            vec![(1, "13140".to_owned()), (2, "##..\n###.".to_owned())]
+        );
+    }
+}
@@ -0,0 +1,346 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+};
+
+// A directed graph with weighted edges. Nodes are numbered in the order they're added, so the
+// searches can keep their state in Vecs rather than maps.
+#[derive(Clone, Debug)]
+pub struct Graph<N> {
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    out_edges: Vec<Vec<(usize, u32)>>,
+    in_edges: Vec<Vec<(usize, u32)>>,
+}
+
+impl<N> Default for Graph<N> {
+    fn default() -> Self {
+        Self {
+            nodes: vec![],
+            indices: HashMap::new(),
+            out_edges: vec![],
+            in_edges: vec![],
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> Graph<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Adding a node that's already in the graph just returns its index.
+    pub fn add_node(&mut self, node: N) -> usize {
+        if let Some(&idx) = self.indices.get(&node) {
+            return idx;
+        }
+        let idx = self.nodes.len();
+        self.nodes.push(node.clone());
+        self.indices.insert(node, idx);
+        self.out_edges.push(vec![]);
+        self.in_edges.push(vec![]);
+        idx
+    }
+
+    pub fn add_edge(&mut self, from: N, to: N, weight: u32) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        self.out_edges[from].push((to, weight));
+        self.in_edges[to].push((from, weight));
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.indices.contains_key(node)
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.nodes.iter()
+    }
+
+    pub fn out_neighbours(&self, node: &N) -> impl Iterator<Item = (&N, u32)> {
+        self.neighbours(&self.out_edges, node)
+    }
+
+    pub fn in_neighbours(&self, node: &N) -> impl Iterator<Item = (&N, u32)> {
+        self.neighbours(&self.in_edges, node)
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        edges: &'a [Vec<(usize, u32)>],
+        node: &N,
+    ) -> impl Iterator<Item = (&'a N, u32)> {
+        self.indices
+            .get(node)
+            .into_iter()
+            .flat_map(move |&idx| &edges[idx])
+            .map(|&(neighbour, weight)| (&self.nodes[neighbour], weight))
+    }
+
+    // The same graph with every edge pointing the other way.
+    pub fn reversed(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+            indices: self.indices.clone(),
+            out_edges: self.in_edges.clone(),
+            in_edges: self.out_edges.clone(),
+        }
+    }
+
+    fn source_indices(&self, sources: impl IntoIterator<Item = N>) -> Vec<usize> {
+        sources
+            .into_iter()
+            .filter_map(|source| self.indices.get(&source).copied())
+            .collect()
+    }
+
+    fn by_node(&self, distances: Vec<Option<u32>>) -> HashMap<N, u32> {
+        distances
+            .into_iter()
+            .enumerate()
+            .filter_map(|(idx, distance)| Some((self.nodes[idx].clone(), distance?)))
+            .collect()
+    }
+
+    // Breadth-first search over `edges`, ignoring the weights. Returns the number of edges to each
+    // node and the node it was first reached from.
+    fn bfs_by_index(
+        &self,
+        edges: &[Vec<(usize, u32)>],
+        sources: &[usize],
+    ) -> (Vec<Option<u32>>, Vec<Option<usize>>) {
+        let mut distances = vec![None; self.len()];
+        let mut predecessors = vec![None; self.len()];
+        let mut queue = VecDeque::new();
+        for &source in sources {
+            distances[source] = Some(0);
+            queue.push_back(source);
+        }
+        while let Some(idx) = queue.pop_front() {
+            let next_distance = distances[idx].unwrap() + 1;
+            for &(neighbour, _) in &edges[idx] {
+                if distances[neighbour].is_none() {
+                    distances[neighbour] = Some(next_distance);
+                    predecessors[neighbour] = Some(idx);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+        (distances, predecessors)
+    }
+
+    // Number of edges on the shortest route from any of the sources to each reachable node,
+    // ignoring the weights.
+    pub fn bfs(&self, sources: impl IntoIterator<Item = N>) -> HashMap<N, u32> {
+        let (distances, _) = self.bfs_by_index(&self.out_edges, &self.source_indices(sources));
+        self.by_node(distances)
+    }
+
+    // Like `bfs`, but keeps enough to reconstruct the routes.
+    pub fn bfs_paths(&self, sources: impl IntoIterator<Item = N>) -> Paths<'_, N> {
+        let (distances, predecessors) =
+            self.bfs_by_index(&self.out_edges, &self.source_indices(sources));
+        Paths {
+            graph: self,
+            distances,
+            predecessors,
+            reversed: false,
+        }
+    }
+
+    // Searches backwards along the edges from the targets, so a single pass finds the shortest
+    // route from every node that can reach any of them.
+    pub fn reverse_bfs_paths(&self, targets: impl IntoIterator<Item = N>) -> Paths<'_, N> {
+        let (distances, predecessors) =
+            self.bfs_by_index(&self.in_edges, &self.source_indices(targets));
+        Paths {
+            graph: self,
+            distances,
+            predecessors,
+            reversed: true,
+        }
+    }
+
+    fn dijkstra_by_index(&self, sources: &[usize]) -> Vec<Option<u32>> {
+        let mut distances = vec![None; self.len()];
+        let mut queue = BinaryHeap::new();
+        for &source in sources {
+            distances[source] = Some(0);
+            queue.push(Reverse((0, source)));
+        }
+        while let Some(Reverse((distance, idx))) = queue.pop() {
+            if distances[idx].is_some_and(|best| best < distance) {
+                continue;
+            }
+            for &(neighbour, weight) in &self.out_edges[idx] {
+                let next_distance = distance + weight;
+                if distances[neighbour].is_none_or(|best| next_distance < best) {
+                    distances[neighbour] = Some(next_distance);
+                    queue.push(Reverse((next_distance, neighbour)));
+                }
+            }
+        }
+        distances
+    }
+
+    // Length of the shortest route from any of the sources to each reachable node.
+    pub fn dijkstra(&self, sources: impl IntoIterator<Item = N>) -> HashMap<N, u32> {
+        self.by_node(self.dijkstra_by_index(&self.source_indices(sources)))
+    }
+
+    // The shortest route from `start` to `goal` and its length. `heuristic` estimates the remaining
+    // distance from a node to the goal, and must never overestimate it.
+    pub fn a_star(
+        &self,
+        start: &N,
+        goal: &N,
+        heuristic: impl Fn(&N) -> u32,
+    ) -> Option<(u32, Vec<N>)> {
+        let start = *self.indices.get(start)?;
+        let goal_idx = *self.indices.get(goal)?;
+        let mut distances = vec![None; self.len()];
+        let mut predecessors = vec![None; self.len()];
+        let mut queue = BinaryHeap::new();
+        distances[start] = Some(0);
+        queue.push(Reverse((heuristic(&self.nodes[start]), 0, start)));
+        while let Some(Reverse((_, distance, idx))) = queue.pop() {
+            if idx == goal_idx {
+                let paths = Paths {
+                    graph: self,
+                    distances,
+                    predecessors,
+                    reversed: false,
+                };
+                return Some((distance, paths.path(goal)?));
+            }
+            if distances[idx].is_some_and(|best| best < distance) {
+                continue;
+            }
+            for &(neighbour, weight) in &self.out_edges[idx] {
+                let next_distance = distance + weight;
+                if distances[neighbour].is_none_or(|best| next_distance < best) {
+                    distances[neighbour] = Some(next_distance);
+                    predecessors[neighbour] = Some(idx);
+                    let estimate = next_distance + heuristic(&self.nodes[neighbour]);
+                    queue.push(Reverse((estimate, next_distance, neighbour)));
+                }
+            }
+        }
+        None
+    }
+
+    // One Dijkstra per node, which beats Floyd-Warshall on the sparse graphs the puzzles have.
+    pub fn all_pairs_shortest_paths(&self) -> DistanceMatrix<N> {
+        let distances = (0..self.len())
+            .flat_map(|source| self.dijkstra_by_index(&[source]))
+            .collect();
+        DistanceMatrix {
+            indices: self.indices.clone(),
+            size: self.len(),
+            distances,
+        }
+    }
+}
+
+// The result of a search that remembers how it reached each node.
+pub struct Paths<'a, N> {
+    graph: &'a Graph<N>,
+    distances: Vec<Option<u32>>,
+    predecessors: Vec<Option<usize>>,
+    reversed: bool,
+}
+
+impl<N: Clone + Eq + Hash> Paths<'_, N> {
+    pub fn distance(&self, node: &N) -> Option<u32> {
+        self.distances[*self.graph.indices.get(node)?]
+    }
+
+    // The route between the search's sources and `node`, following the direction of the edges:
+    // from a source to `node`, or for a reverse search, from `node` to a target.
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        let mut idx = *self.graph.indices.get(node)?;
+        self.distances[idx]?;
+        let mut result = vec![self.graph.nodes[idx].clone()];
+        while let Some(prev) = self.predecessors[idx] {
+            result.push(self.graph.nodes[prev].clone());
+            idx = prev;
+        }
+        if !self.reversed {
+            result.reverse();
+        }
+        Some(result)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DistanceMatrix<N> {
+    indices: HashMap<N, usize>,
+    size: usize,
+    distances: Vec<Option<u32>>,
+}
+
+impl<N: Eq + Hash> DistanceMatrix<N> {
+    // None if either node isn't in the graph, or `to` can't be reached from `from`.
+    pub fn get(&self, from: &N, to: &N) -> Option<u32> {
+        let from = self.indices.get(from)?;
+        let to = self.indices.get(to)?;
+        self.distances[from * self.size + to]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Graph;
+
+    // a -> b -> c is cheaper than a -> c, and d can only reach a.
+    fn example() -> Graph<char> {
+        let mut graph = Graph::new();
+        graph.add_edge('a', 'b', 2);
+        graph.add_edge('b', 'c', 3);
+        graph.add_edge('a', 'c', 10);
+        graph.add_edge('d', 'a', 1);
+        graph
+    }
+
+    #[test]
+    fn searches_agree() {
+        let graph = example();
+        let distances = graph.dijkstra(['a']);
+        assert_eq!(distances[&'c'], 5);
+        assert!(!distances.contains_key(&'d'));
+        assert_eq!(graph.bfs(['a'])[&'c'], 1);
+        assert_eq!(
+            graph.a_star(&'a', &'c', |_| 0),
+            Some((5, vec!['a', 'b', 'c']))
+        );
+        assert_eq!(graph.a_star(&'c', &'a', |_| 0), None);
+
+        let all_pairs = graph.all_pairs_shortest_paths();
+        assert_eq!(all_pairs.get(&'d', &'c'), Some(6));
+        assert_eq!(all_pairs.get(&'c', &'c'), Some(0));
+        assert_eq!(all_pairs.get(&'c', &'d'), None);
+    }
+
+    #[test]
+    fn multiple_sources_and_reversal() {
+        let graph = example();
+        assert_eq!(graph.dijkstra(['a', 'b'])[&'c'], 3);
+        let reversed = graph.reversed();
+        assert_eq!(reversed.dijkstra(['c'])[&'d'], 6);
+        assert_eq!(graph.in_neighbours(&'c').count(), 2);
+
+        let paths = graph.reverse_bfs_paths(['c']);
+        assert_eq!(paths.distance(&'d'), Some(2));
+        assert_eq!(paths.path(&'d'), Some(vec!['d', 'a', 'c']));
+        assert_eq!(graph.bfs_paths(['d']).path(&'c'), Some(vec!['d', 'a', 'c']));
+    }
+}
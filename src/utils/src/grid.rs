@@ -0,0 +1,342 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use crate::{numbered_lines, Line, ParseError};
+
+// Positions are (x, y), i.e. (column, row), with (0, 0) in the top left.
+pub type Position = (usize, usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub fn offset(&self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+}
+
+const DIAGONAL_OFFSETS: [(isize, isize); 4] = [(-1, -1), (1, -1), (-1, 1), (1, 1)];
+
+// A rectangular grid, stored row by row in a single Vec.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    // Panics if the rows aren't all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "expected every row of the grid to have {width} cells"
+        );
+        Self {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    // Builds a grid from character art, one row per line. Every line must be the same length.
+    pub fn parse(
+        input: &str,
+        mut parse_cell: impl FnMut(&Line, &str, char) -> Result<T, ParseError>,
+    ) -> Result<Self, ParseError> {
+        let mut rows = vec![];
+        let mut width = None;
+        for line in numbered_lines(input) {
+            let row = line
+                .text
+                .char_indices()
+                .map(|(idx, ch)| parse_cell(&line, &line.text[idx..idx + ch.len_utf8()], ch))
+                .collect::<Result<Vec<_>, _>>()?;
+            let width = *width.get_or_insert(row.len());
+            if row.len() != width {
+                let expected = format!("a row {width} cells wide");
+                return Err(match line.text.char_indices().nth(width) {
+                    Some((idx, _)) => line.error(&line.text[idx..], expected),
+                    None => line.error_at_end(expected),
+                });
+            }
+            rows.push(row);
+        }
+        Ok(Self::from_rows(rows))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Position) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, (x, y): Position) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    // The position `offset` away from `position`, if it's inside the grid.
+    pub fn offset(&self, (x, y): Position, (dx, dy): (isize, isize)) -> Option<Position> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    pub fn step(&self, position: Position, direction: Direction) -> Option<Position> {
+        self.offset(position, direction.offset())
+    }
+
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction))
+    }
+
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL
+            .iter()
+            .map(Direction::offset)
+            .chain(DIAGONAL_OFFSETS)
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    // Every position from `start` (exclusive) to the edge of the grid in `direction`.
+    pub fn ray(
+        &self,
+        start: Position,
+        direction: Direction,
+    ) -> impl Iterator<Item = Position> + '_ {
+        std::iter::successors(self.step(start, direction), move |&position| {
+            self.step(position, direction)
+        })
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+
+    // Panics if `x` is off the grid, like `row` does for `y`, rather than running into other rows.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {x} is outside a grid {} wide",
+            self.width
+        );
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn positions_where(&self, predicate: impl Fn(&T) -> bool) -> Vec<Position> {
+        self.iter()
+            .filter_map(|(position, val)| predicate(val).then_some(position))
+            .collect()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // Panics if the grids aren't the same size.
+    pub fn zip_with<U, V>(&self, other: &Grid<U>, mut f: impl FnMut(&T, &U) -> V) -> Grid<V> {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "expected grids of the same size"
+        );
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self
+                .cells
+                .iter()
+                .zip(other.cells.iter())
+                .map(|(a, b)| f(a, b))
+                .collect(),
+        }
+    }
+
+    // Replaces each row with the result of `f`, which has to return rows of equal length.
+    pub fn map_rows<U>(&self, f: impl FnMut(&[T]) -> Vec<U>) -> Grid<U> {
+        Grid::from_rows(self.rows().map(f).collect())
+    }
+
+    // Adds a row to the bottom of the grid, growing it downwards. Panics if the row is the wrong
+    // width, unless the grid is empty.
+    pub fn push_row(&mut self, row: Vec<T>) {
+        if self.cells.is_empty() {
+            self.width = row.len();
+        }
+        assert_eq!(
+            row.len(),
+            self.width,
+            "expected a row {} cells wide",
+            self.width
+        );
+        self.cells.extend(row);
+        self.height += 1;
+    }
+
+    // Draws the grid one row per line, with `render` choosing the character for each cell.
+    pub fn render(&self, mut render: impl FnMut(Position, &T) -> char) -> String {
+        let mut result = String::with_capacity((self.width + 1) * self.height);
+        for (position, val) in self.iter() {
+            result.push(render(position, val));
+            if position.0 + 1 == self.width {
+                result.push('\n');
+            }
+        }
+        result
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn transpose(&self) -> Self {
+        Self {
+            width: self.height,
+            height: self.width,
+            cells: (0..self.width)
+                .flat_map(|x| self.column(x).cloned())
+                .collect(),
+        }
+    }
+
+    // Rotates the grid clockwise by `n` quarter turns.
+    pub fn rotate(&self, n: u32) -> Self {
+        (0..n % 4).fold(self.clone(), |acc, _| {
+            let transposed = acc.transpose();
+            transposed.map_rows(|row| row.iter().rev().cloned().collect())
+        })
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "position {position:?} is outside the {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("position {position:?} is outside the {width}x{height} grid"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for val in row {
+                write!(f, "{val}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, Grid};
+
+    fn example() -> Grid<char> {
+        Grid::parse("abc\ndef", |_, _, ch| Ok(ch)).unwrap()
+    }
+
+    #[test]
+    fn rotate_and_transpose() {
+        let grid = example();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate(1).to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate(2).to_string(), "fed\ncba\n");
+        assert_eq!(grid.rotate(4), grid);
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid = example();
+        let mut corner: Vec<_> = grid.neighbours4((0, 0)).collect();
+        corner.sort();
+        assert_eq!(corner, vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbours8((1, 0)).count(), 5);
+        assert_eq!(
+            grid.ray((0, 1), Direction::Right).collect::<Vec<_>>(),
+            vec![(1, 1), (2, 1)]
+        );
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.column(2).collect::<String>(), "cf");
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is outside a grid 3 wide")]
+    fn column_off_the_grid() {
+        example().column(3).count();
+    }
+
+    #[test]
+    fn ragged_rows_are_a_parse_error() {
+        let err = Grid::parse("abc\nde", |_, _, ch| Ok(ch)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+}
@@ -0,0 +1,140 @@
+use std::{
+    fmt::Display,
+    fs::read_to_string,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    // A file next to the day's Cargo.toml, e.g. "example" for example.txt or "input.alt" for
+    // input.alt.txt. The default is "input", i.e. the usual input.txt.
+    Variant(String),
+    Path(PathBuf),
+    Stdin,
+}
+
+impl Default for InputSource {
+    fn default() -> Self {
+        Self::Variant("input".to_owned())
+    }
+}
+
+impl InputSource {
+    // "-" means stdin, as is the convention for most command line tools.
+    pub fn from_path_arg(arg: &str) -> Self {
+        if arg == "-" {
+            Self::Stdin
+        } else {
+            Self::Path(PathBuf::from(arg))
+        }
+    }
+
+    // Understands `--input <path>` (or `--input -` for stdin) and `--variant <name>`. Used by the
+    // per-day binaries, which don't otherwise need an argument parser.
+    pub fn from_args(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut result = Self::default();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .ok_or_else(|| format!("expected a value after {arg}"))
+            };
+            result = match arg.as_str() {
+                "--input" => Self::from_path_arg(&value()?),
+                "--variant" => Self::Variant(value()?),
+                other => return Err(format!("unexpected argument {other}")),
+            };
+        }
+        Ok(result)
+    }
+
+    // Short description for reports, e.g. "example" or "stdin".
+    pub fn name(&self) -> String {
+        match self {
+            Self::Variant(name) => name.clone(),
+            Self::Path(path) => path.display().to_string(),
+            Self::Stdin => "stdin".to_owned(),
+        }
+    }
+
+    fn variant_path(input_dir: &str, name: &str) -> PathBuf {
+        let file_name = if name.ends_with(".txt") {
+            name.to_owned()
+        } else {
+            format!("{name}.txt")
+        };
+        Path::new(input_dir).join(file_name)
+    }
+
+    pub fn read(&self, input_dir: &str) -> Result<String, InputError> {
+        let (location, result) = match self {
+            Self::Variant(name) => {
+                let path = Self::variant_path(input_dir, name);
+                (path.display().to_string(), read_to_string(&path))
+            }
+            Self::Path(path) => (path.display().to_string(), read_to_string(path)),
+            Self::Stdin => {
+                let mut buffer = String::new();
+                let result = io::stdin().read_to_string(&mut buffer).map(|_| buffer);
+                ("stdin".to_owned(), result)
+            }
+        };
+        result.map_err(|error| InputError { location, error })
+    }
+
+    // `<name>.settings` next to a variant's `<name>.txt`, or next to a file given by path.
+    fn settings_path(&self, input_dir: &str) -> Option<PathBuf> {
+        match self {
+            Self::Variant(name) => {
+                let name = name.strip_suffix(".txt").unwrap_or(name);
+                Some(Path::new(input_dir).join(format!("{name}.settings")))
+            }
+            Self::Path(path) => Some(path.with_extension("settings")),
+            Self::Stdin => None,
+        }
+    }
+
+    // Empty unless there's a settings file for the input.
+    pub fn read_settings(&self, input_dir: &str) -> Result<String, InputError> {
+        let Some(path) = self.settings_path(input_dir) else {
+            return Ok(String::new());
+        };
+        match read_to_string(&path) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+            result => result.map_err(|error| InputError {
+                location: path.display().to_string(),
+                error,
+            }),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    location: String,
+    error: io::Error,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "failed to read input from {}: {}",
+            self.location, self.error
+        )
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+pub fn read_input(input_dir: &str, source: &InputSource) -> Result<String, InputError> {
+    source.read(input_dir)
+}
+
+pub fn read_settings(input_dir: &str, source: &InputSource) -> Result<String, InputError> {
+    source.read_settings(input_dir)
+}
@@ -1,7 +1,197 @@
-use std::{env, fs::read_to_string, path::Path};
+use std::{env, fmt::Display, process, str::FromStr};
 
-pub fn read_input() -> String {
-    let cargo_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
-    let input_path = Path::new(&cargo_dir).join("input.txt");
-    read_to_string(input_path).unwrap()
+mod answers;
+mod graph;
+mod grid;
+mod input;
+mod parse;
+mod timing;
+
+pub use answers::{check_answers, parse_answers};
+pub use graph::{DistanceMatrix, Graph, Paths};
+pub use grid::{Direction, Grid, Position};
+pub use input::{read_input, read_settings, InputError, InputSource};
+pub use parse::{numbered_lines, parse_lines, Line, ParseError, Tokens};
+pub use timing::{solve_timed, Bench, Stats, Timings};
+
+pub trait Solution {
+    const DAY: u8;
+    // Each day sets this to its own env!("CARGO_MANIFEST_DIR") so that its input can be found
+    // regardless of which binary is running it.
+    const INPUT_DIR: &'static str;
+
+    type Input;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    // For days where some inputs need more than the puzzle text says, like day 15's example
+    // searching a smaller area than the real puzzle. `settings` is the input's `.settings` file,
+    // or empty if it hasn't got one.
+    fn parse_with_settings(input: &str, _settings: &str) -> Result<Self::Input, ParseError> {
+        Self::parse(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1;
+    fn part2(input: &Self::Input) -> Self::Part2;
+}
+
+pub struct Answers {
+    pub part_1: String,
+    pub part_2: String,
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "part 1:{}", format_answer(&self.part_1))?;
+        writeln!(f, "part 2:{}", format_answer(&self.part_2))
+    }
+}
+
+// Multi-line answers (like day 10's screen) start on their own line.
+fn format_answer(answer: &str) -> String {
+    if answer.contains('\n') {
+        format!("\n{}", answer.trim_end())
+    } else {
+        format!(" {}", answer)
+    }
+}
+
+#[derive(Debug)]
+pub enum PuzzleError {
+    Input(InputError),
+    Parse(ParseError),
+}
+
+impl Display for PuzzleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Input(err) => err.fmt(f),
+            Self::Parse(err) => write!(f, "failed to parse input: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for PuzzleError {}
+
+impl From<InputError> for PuzzleError {
+    fn from(err: InputError) -> Self {
+        Self::Input(err)
+    }
+}
+
+impl From<ParseError> for PuzzleError {
+    fn from(err: ParseError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+pub fn solve<S: Solution>(input: &str, settings: &str) -> Result<Answers, ParseError> {
+    solve_timed::<S>(input, settings).map(|(answers, _)| answers)
+}
+
+// Solves a day's puzzle from its input and settings.
+pub type SolveFn = fn(&str, &str) -> Result<(Answers, Timings), ParseError>;
+
+// Type-erased handle on a day's solution, so a runner can hold every day in one list.
+pub struct Puzzle {
+    pub day: u8,
+    pub input_dir: &'static str,
+    pub solve: SolveFn,
+}
+
+impl Puzzle {
+    pub fn of<S: Solution>() -> Self {
+        Self {
+            day: S::DAY,
+            input_dir: S::INPUT_DIR,
+            solve: solve_timed::<S>,
+        }
+    }
+
+    pub fn run(&self, source: &InputSource) -> Result<(Answers, Timings), PuzzleError> {
+        let input = read_input(self.input_dir, source)?;
+        let settings = read_settings(self.input_dir, source)?;
+        Ok((self.solve)(&input, &settings)?)
+    }
+
+    // Solves the puzzle `runs` times on the same input, which is only read once.
+    pub fn bench(
+        &self,
+        source: &InputSource,
+        runs: usize,
+    ) -> Result<(Answers, Bench), PuzzleError> {
+        let input = read_input(self.input_dir, source)?;
+        let settings = read_settings(self.input_dir, source)?;
+        let mut answers = None;
+        let mut timings = vec![];
+        for _ in 0..runs.max(1) {
+            let (run_answers, run_timings) = (self.solve)(&input, &settings)?;
+            answers = Some(run_answers);
+            timings.push(run_timings);
+        }
+        Ok((answers.unwrap(), Bench::of(&timings)))
+    }
+}
+
+// Removes `flag` from the arguments if it's there, for per-day binaries with extra modes.
+pub fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let len_before = args.len();
+    args.retain(|arg| arg != flag);
+    args.len() != len_before
+}
+
+// Removes `name` and the value after it from the arguments, e.g. `--width 9`.
+pub fn take_value(args: &mut Vec<String>, name: &str) -> Result<Option<String>, String> {
+    let Some(idx) = args.iter().position(|arg| arg == name) else {
+        return Ok(None);
+    };
+    if idx + 1 == args.len() {
+        return Err(format!("expected a value after {name}"));
+    }
+    let value = args.remove(idx + 1);
+    args.remove(idx);
+    Ok(Some(value))
+}
+
+pub fn take_number<T: FromStr>(args: &mut Vec<String>, name: &str) -> Result<Option<T>, String> {
+    take_value(args, name)?
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| format!("expected a number after {name}, found {value}"))
+        })
+        .transpose()
+}
+
+pub fn exit_with_error(message: impl Display) -> ! {
+    eprintln!("error: {message}");
+    process::exit(1);
+}
+
+// Reads and parses the input named by `args`, for per-day binaries that do more than print the
+// answers.
+pub fn load_input<S: Solution>(args: impl Iterator<Item = String>) -> S::Input {
+    let source = InputSource::from_args(args).unwrap_or_else(|message| exit_with_error(message));
+    let input = read_input(S::INPUT_DIR, &source).unwrap_or_else(|err| exit_with_error(err));
+    let settings = read_settings(S::INPUT_DIR, &source).unwrap_or_else(|err| exit_with_error(err));
+    S::parse_with_settings(&input, &settings)
+        .unwrap_or_else(|err| exit_with_error(PuzzleError::Parse(err)))
+}
+
+pub fn run_with_args<S: Solution>(args: impl Iterator<Item = String>) {
+    let result = InputSource::from_args(args).and_then(|source| {
+        Puzzle::of::<S>()
+            .run(&source)
+            .map_err(|err| err.to_string())
+    });
+    match result {
+        Ok((answers, _)) => print!("{answers}"),
+        Err(message) => exit_with_error(message),
+    }
+}
+
+pub fn run<S: Solution>() {
+    run_with_args::<S>(env::args().skip(1));
 }
@@ -0,0 +1,208 @@
+use std::{fmt::Display, str::FromStr};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    // Both 1-based, to match what an editor shows.
+    pub line: usize,
+    pub column: usize,
+    pub found: String,
+    pub expected: String,
+    pub line_text: String,
+}
+
+impl ParseError {
+    // For input that stops before something required. There's no line to point at, so `line` is 0.
+    pub fn end_of_input(expected: impl Into<String>) -> Self {
+        Self {
+            line: 0,
+            column: 0,
+            found: String::new(),
+            expected: expected.into(),
+            line_text: String::new(),
+        }
+    }
+
+    // For input whose lines all parse but that's wrong as a whole, like a missing start square.
+    // There's no one line to blame either, so `line` is 0 too, but `found` says what's wrong.
+    pub fn invalid_input(expected: impl Into<String>, found: impl Into<String>) -> Self {
+        Self {
+            found: found.into(),
+            ..Self::end_of_input(expected)
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.line, self.found.is_empty()) {
+            (0, true) => return write!(f, "end of input: expected {}", self.expected),
+            (0, false) => {
+                return write!(f, "input: expected {}, found {}", self.expected, self.found)
+            }
+            _ => {}
+        }
+        let found = if self.found.is_empty() {
+            "end of line".to_owned()
+        } else {
+            format!("{:?}", self.found)
+        };
+        writeln!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, found
+        )?;
+        writeln!(f, "    {}", self.line_text)?;
+        write!(f, "    {}^", " ".repeat(self.column.saturating_sub(1)))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(idx, text)| Line {
+        number: idx + 1,
+        text,
+    })
+}
+
+// Parses every line with `parse_line`, stopping at the first error.
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    parse_line: impl FnMut(Line<'a>) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    numbered_lines(input).map(parse_line).collect()
+}
+
+impl<'a> Line<'a> {
+    // Where `found` starts in this line, if it's a slice of it. Anything else is reported at the
+    // start of the line.
+    fn column_of(&self, found: &str) -> usize {
+        let line_start = self.text.as_ptr() as usize;
+        let found_start = found.as_ptr() as usize;
+        if found_start >= line_start && found_start <= line_start + self.text.len() {
+            found_start - line_start + 1
+        } else {
+            1
+        }
+    }
+
+    // `found` should be a slice of the line's text so that the column can be worked out.
+    pub fn error(&self, found: &str, expected: impl Into<String>) -> ParseError {
+        self.error_at(self.column_of(found), found, expected)
+    }
+
+    pub fn error_at(&self, column: usize, found: &str, expected: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.number,
+            column,
+            found: found.to_owned(),
+            expected: expected.into(),
+            line_text: self.text.to_owned(),
+        }
+    }
+
+    pub fn error_at_end(&self, expected: impl Into<String>) -> ParseError {
+        self.error(&self.text[self.text.len()..], expected)
+    }
+
+    pub fn parse<T: FromStr>(&self, token: &str, expected: &str) -> Result<T, ParseError> {
+        token.parse().map_err(|_| self.error(token, expected))
+    }
+
+    pub fn strip_prefix(&self, text: &'a str, prefix: &str) -> Result<&'a str, ParseError> {
+        text.strip_prefix(prefix)
+            .ok_or_else(|| self.error(text, format!("text starting with {prefix:?}")))
+    }
+
+    pub fn strip_suffix(&self, text: &'a str, suffix: &str) -> Result<&'a str, ParseError> {
+        text.strip_suffix(suffix)
+            .ok_or_else(|| self.error(text, format!("text ending with {suffix:?}")))
+    }
+
+    // Whitespace separated tokens, so that a missing one can be reported at the end of the line.
+    pub fn tokens(&self) -> Tokens<'a> {
+        Tokens {
+            line: *self,
+            tokens: self.text.split_ascii_whitespace().collect(),
+        }
+    }
+}
+
+pub struct Tokens<'a> {
+    line: Line<'a>,
+    tokens: Vec<&'a str>,
+}
+
+impl<'a> Tokens<'a> {
+    pub fn get(&self, idx: usize, expected: &str) -> Result<&'a str, ParseError> {
+        self.tokens
+            .get(idx)
+            .cloned()
+            .ok_or_else(|| self.line.error_at_end(expected))
+    }
+
+    pub fn rest(&self, from_idx: usize) -> &[&'a str] {
+        self.tokens.get(from_idx..).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{numbered_lines, parse_lines, Line, ParseError};
+
+    fn parse_pair(line: Line) -> Result<(u32, u32), ParseError> {
+        let tokens = line.tokens();
+        let first = line.parse(tokens.get(0, "a number")?, "a number")?;
+        let second = line.parse(tokens.get(1, "a second number")?, "a number")?;
+        Ok((first, second))
+    }
+
+    #[test]
+    fn errors_point_at_what_was_found() {
+        let err = parse_lines("1 2\n3  x4\n", parse_pair).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(err.found, "x4");
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 4: expected a number, found \"x4\"\n    3  x4\n       ^"
+        );
+
+        let err = parse_lines("1 2\n3\n", parse_pair).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.expected, "a second number");
+        assert!(err
+            .to_string()
+            .starts_with("line 2, column 2: expected a second number, found end of line\n"));
+    }
+
+    #[test]
+    fn prefixes_and_suffixes() {
+        let line = numbered_lines("x=12,").next().unwrap();
+        assert_eq!(line.strip_prefix(line.text, "x="), Ok("12,"));
+        let err = line.strip_suffix(line.text, ":").unwrap_err();
+        assert_eq!(
+            (err.column, err.expected.as_str()),
+            (1, "text ending with \":\"")
+        );
+        let err = line.strip_prefix(&line.text[2..], "y=").unwrap_err();
+        assert_eq!((err.column, err.found.as_str()), (3, "12,"));
+    }
+
+    #[test]
+    fn errors_about_the_whole_input() {
+        assert_eq!(
+            ParseError::end_of_input("another line").to_string(),
+            "end of input: expected another line"
+        );
+        assert_eq!(
+            ParseError::invalid_input("exactly one start", "2").to_string(),
+            "input: expected exactly one start, found 2"
+        );
+    }
+}
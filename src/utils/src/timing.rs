@@ -0,0 +1,122 @@
+use std::{
+    fmt::Display,
+    time::{Duration, Instant},
+};
+
+use crate::{Answers, ParseError, Solution};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part_1: Duration,
+    pub part_2: Duration,
+}
+
+impl Timings {
+    pub fn stages(&self) -> [(&'static str, Duration); 3] {
+        [
+            ("parse", self.parse),
+            ("part 1", self.part_1),
+            ("part 2", self.part_2),
+        ]
+    }
+}
+
+impl Display for Timings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (stage, duration) in self.stages() {
+            writeln!(f, "{stage} took {duration:.2?}")?;
+        }
+        Ok(())
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+pub fn solve_timed<S: Solution>(
+    input: &str,
+    settings: &str,
+) -> Result<(Answers, Timings), ParseError> {
+    let (parsed, parse) = timed(|| S::parse_with_settings(input, settings));
+    let parsed = parsed?;
+    // Converting the answers to strings is left out of the timings.
+    let (part_1, part_1_duration) = timed(|| S::part1(&parsed));
+    let (part_2, part_2_duration) = timed(|| S::part2(&parsed));
+    let answers = Answers {
+        part_1: part_1.to_string(),
+        part_2: part_2.to_string(),
+    };
+    let timings = Timings {
+        parse,
+        part_1: part_1_duration,
+        part_2: part_2_duration,
+    };
+    Ok((answers, timings))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    // Panics if there are no samples.
+    pub fn of(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        Self {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+pub struct Bench {
+    pub runs: usize,
+    pub stages: Vec<(&'static str, Stats)>,
+}
+
+impl Bench {
+    pub fn of(runs: &[Timings]) -> Self {
+        let stages = Timings::default()
+            .stages()
+            .iter()
+            .enumerate()
+            .map(|(stage_idx, (stage, _))| {
+                let samples = runs.iter().map(|run| run.stages()[stage_idx].1).collect();
+                (*stage, Stats::of(samples))
+            })
+            .collect();
+        Self {
+            runs: runs.len(),
+            stages,
+        }
+    }
+}
+
+impl Display for Bench {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:<8}{:>12}{:>12}{:>12}   ({} runs)",
+            "stage", "min", "median", "max", self.runs
+        )?;
+        for (stage, stats) in &self.stages {
+            writeln!(
+                f,
+                "{:<8}{:>12}{:>12}{:>12}",
+                stage,
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.max)
+            )?;
+        }
+        Ok(())
+    }
+}